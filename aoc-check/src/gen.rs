@@ -0,0 +1,180 @@
+use aoc_core::Rng;
+use std::fmt::Debug;
+
+/// Generates random test cases, and simpler versions of a failing one.
+pub trait Gen {
+    type Value: Clone + Debug;
+
+    /// A random value; `size` (which grows over the run) bounds how large it gets.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Self::Value;
+
+    /// Values a little simpler than `value`, simplest first. The shrinking stops at a value with
+    /// none that still fails.
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value>;
+}
+
+/// Integers in `low..=high`, shrinking towards `low`.
+pub struct Ints {
+    low: u64,
+    high: u64,
+}
+
+pub fn ints(low: u64, high: u64) -> Ints {
+    assert!(low <= high, "expected a non-empty range");
+    Ints { low, high }
+}
+
+impl Gen for Ints {
+    type Value = u64;
+
+    fn generate(&self, rng: &mut Rng, _size: usize) -> u64 {
+        // Favour the ends of the range, where the mistakes tend to be.
+        match rng.below(8) {
+            0 => self.low,
+            1 => self.high,
+            _ => rng.between(self.low, self.high),
+        }
+    }
+
+    fn shrink(&self, &value: &u64) -> Vec<u64> {
+        // Jump to `low`, then ever closer to `value`, so that finding the edge of a failure takes
+        // a logarithmic number of steps.
+        let mut simpler = vec![];
+        let mut distance = value - self.low;
+        while distance > 0 {
+            simpler.push(value - distance);
+            distance /= 2;
+        }
+        simpler
+    }
+}
+
+/// Vectors of up to `size` elements (and at least `min_len`), shrinking by dropping elements and
+/// by shrinking each element.
+pub struct Vecs<G> {
+    element: G,
+    min_len: usize,
+}
+
+pub fn vecs<G: Gen>(element: G, min_len: usize) -> Vecs<G> {
+    Vecs { element, min_len }
+}
+
+impl<G: Gen> Gen for Vecs<G> {
+    type Value = Vec<G::Value>;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Self::Value {
+        let len = rng.between(self.min_len as u64, size.max(self.min_len) as u64);
+        (0..len).map(|_| self.element.generate(rng, size)).collect()
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let mut simpler = vec![];
+        // Drop halves, then single elements.
+        let len = value.len();
+        if len / 2 >= self.min_len && len > 1 {
+            simpler.push(value[..len / 2].to_vec());
+            simpler.push(value[len / 2..].to_vec());
+        }
+        if len > self.min_len {
+            for i in 0..len {
+                let mut dropped = value.clone();
+                dropped.remove(i);
+                simpler.push(dropped);
+            }
+        }
+        for (i, element) in value.iter().enumerate() {
+            for element in self.element.shrink(element) {
+                let mut shrunk = value.clone();
+                shrunk[i] = element;
+                simpler.push(shrunk);
+            }
+        }
+        simpler
+    }
+}
+
+/// Strings of up to `size` characters from `alphabet` (and at least `min_len`), shrinking by
+/// dropping characters and by replacing them with ones earlier in the alphabet.
+pub struct Strings {
+    chars: Vecs<Ints>,
+    alphabet: Vec<char>,
+}
+
+pub fn strings(alphabet: &str, min_len: usize) -> Strings {
+    let alphabet: Vec<char> = alphabet.chars().collect();
+    assert!(!alphabet.is_empty(), "expected an alphabet");
+    Strings {
+        chars: vecs(ints(0, alphabet.len() as u64 - 1), min_len),
+        alphabet,
+    }
+}
+
+impl Strings {
+    fn indices(&self, value: &str) -> Vec<u64> {
+        value
+            .chars()
+            .map(|c| self.alphabet.iter().position(|&a| a == c).unwrap() as u64)
+            .collect()
+    }
+
+    fn string(&self, indices: &[u64]) -> String {
+        indices.iter().map(|&i| self.alphabet[i as usize]).collect()
+    }
+}
+
+impl Gen for Strings {
+    type Value = String;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        self.string(&self.chars.generate(rng, size))
+    }
+
+    fn shrink(&self, value: &String) -> Vec<String> {
+        let indices = self.indices(value);
+        self.chars
+            .shrink(&indices)
+            .iter()
+            .map(|indices| self.string(indices))
+            .collect()
+    }
+}
+
+/// Pairs, shrinking one side at a time.
+impl<A: Gen, B: Gen> Gen for (A, B) {
+    type Value = (A::Value, B::Value);
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Self::Value {
+        (self.0.generate(rng, size), self.1.generate(rng, size))
+    }
+
+    fn shrink(&self, (a, b): &Self::Value) -> Vec<Self::Value> {
+        let mut simpler: Vec<_> = self
+            .0
+            .shrink(a)
+            .into_iter()
+            .map(|a| (a, b.clone()))
+            .collect();
+        simpler.extend(self.1.shrink(b).into_iter().map(|b| (a.clone(), b)));
+        simpler
+    }
+}
+
+#[test]
+fn shrinking() {
+    assert_eq!(ints(3, 100).shrink(&10), [3, 7, 9]);
+    assert!(ints(3, 100).shrink(&3).is_empty());
+
+    let vecs = vecs(ints(0, 9), 1);
+    let simpler = vecs.shrink(&vec![4, 0]);
+    assert_eq!(simpler[..4], [vec![4], vec![0], vec![0], vec![4]]);
+    assert!(simpler.iter().all(|v| !v.is_empty()));
+
+    let strings = strings("abc", 0);
+    assert!(strings.shrink(&"c".to_string()).contains(&"a".to_string()));
+    let mut rng = Rng::new(1);
+    for _ in 0..100 {
+        let s = strings.generate(&mut rng, 5);
+        assert!(s.len() <= 5 && s.chars().all(|c| "abc".contains(c)));
+    }
+}
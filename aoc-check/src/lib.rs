@@ -0,0 +1,202 @@
+//! Property testing, and differential testing of optimised code against a naive reference, with
+//! the failing cases shrunk to a minimal one before they are reported.
+//!
+//! The cases come from a seeded `Rng`, so a failure can be replayed. The seed, and the number of
+//! cases, can be changed with the `AOC_CHECK_SEED` and `AOC_CHECK_CASES` environment variables.
+
+mod gen;
+
+pub use gen::{ints, strings, vecs, Gen, Ints, Strings, Vecs};
+
+use aoc_core::Rng;
+use std::{
+    cell::Cell,
+    env,
+    fmt::Debug,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+pub const SEED_VAR: &str = "AOC_CHECK_SEED";
+pub const CASES_VAR: &str = "AOC_CHECK_CASES";
+
+pub struct Check {
+    seed: u64,
+    cases: usize,
+    max_size: usize,
+}
+
+impl Default for Check {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Check {
+    /// 200 cases of up to size 50, unless the environment says otherwise.
+    pub fn new() -> Self {
+        let var = |name| env::var(name).ok().and_then(|v| v.parse().ok());
+        Check {
+            seed: var(SEED_VAR).unwrap_or(0),
+            cases: var(CASES_VAR).map(|cases| cases as usize).unwrap_or(200),
+            max_size: 50,
+        }
+    }
+
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// How large the last cases get; the first ones are smaller.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Checks that the `property` holds (returns `Ok` without panicking) for every case, and
+    /// panics with the simplest failing case otherwise.
+    pub fn property<G: Gen>(&self, gen: &G, property: impl Fn(&G::Value) -> Result<(), String>) {
+        let mut rng = Rng::new(self.seed);
+        for case in 0..self.cases {
+            let size = 1 + case * self.max_size / self.cases.max(1);
+            let value = gen.generate(&mut rng, size);
+            if let Err(message) = holds(&property, &value) {
+                let (value, message, steps) = shrink(gen, &property, value, message);
+                panic!(
+                    "property failed after {} cases (seed {}), shrunk {} times to:\n{:?}\n{}",
+                    case + 1,
+                    self.seed,
+                    steps,
+                    value,
+                    message
+                );
+            }
+        }
+    }
+
+    /// Checks that the `optimized` code gives the same result as the `reference` for every case.
+    pub fn differential<G: Gen, T: PartialEq + Debug>(
+        &self,
+        gen: &G,
+        optimized: impl Fn(&G::Value) -> T,
+        reference: impl Fn(&G::Value) -> T,
+    ) {
+        self.property(gen, |value| {
+            let expected = reference(value);
+            let got = optimized(value);
+            if got == expected {
+                Ok(())
+            } else {
+                Err(format!("expected {:?}, got {:?}", expected, got))
+            }
+        })
+    }
+}
+
+/// Runs the property, turning a panic into a failure.
+fn holds<T>(property: impl Fn(&T) -> Result<(), String>, value: &T) -> Result<(), String> {
+    quiet_panics();
+    QUIET.with(|quiet| quiet.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| property(value)));
+    QUIET.with(|quiet| quiet.set(false));
+    match result {
+        Ok(result) => result,
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| panic.downcast_ref::<&str>().copied())
+                .unwrap_or("(no message)");
+            Err(format!("panicked: {}", message))
+        }
+    }
+}
+
+/// Simplifies a failing value for as long as it keeps failing, returning the simplest one found,
+/// its failure, and how many steps it took.
+fn shrink<G: Gen>(
+    gen: &G,
+    property: impl Fn(&G::Value) -> Result<(), String>,
+    mut value: G::Value,
+    mut message: String,
+) -> (G::Value, String, usize) {
+    let mut steps = 0;
+    'simpler: loop {
+        for simpler in gen.shrink(&value) {
+            if let Err(simpler_message) = holds(&property, &simpler) {
+                value = simpler;
+                message = simpler_message;
+                steps += 1;
+                continue 'simpler;
+            }
+        }
+        return (value, message, steps);
+    }
+}
+
+thread_local! {
+    /// Whether panics on this thread are expected, and shouldn't be printed.
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Keeps the panics caught while checking from being printed, leaving the others alone.
+fn quiet_panics() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                default(info);
+            }
+        }));
+    });
+}
+
+#[test]
+fn passing() {
+    Check::new().property(&vecs(ints(0, 100), 0), |v| {
+        match v.iter().sum::<u64>() <= 100 * v.len() as u64 {
+            true => Ok(()),
+            false => Err("sum too large".to_string()),
+        }
+    });
+}
+
+#[test]
+fn minimal_case() {
+    // A "sort" that loses track of anything above 50.
+    let optimized = |v: &Vec<u64>| {
+        let mut v: Vec<u64> = v.iter().map(|&x| x.min(50)).collect();
+        v.sort();
+        v
+    };
+    let reference = |v: &Vec<u64>| {
+        let mut v = v.clone();
+        v.sort();
+        v
+    };
+    let failure = panic::catch_unwind(|| {
+        Check::new().differential(&vecs(ints(0, 100), 0), optimized, reference)
+    })
+    .unwrap_err();
+    let message = failure.downcast_ref::<String>().unwrap();
+    assert!(
+        message.contains("\n[51]\nexpected [51], got [50]"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn panics_are_failures() {
+    let failure = panic::catch_unwind(|| {
+        Check::new().property(&ints(0, 1000), |&x| {
+            assert!(x < 500, "too big");
+            Ok(())
+        })
+    })
+    .unwrap_err();
+    let message = failure.downcast_ref::<String>().unwrap();
+    assert!(message.contains("\n500\npanicked: too big"), "{}", message);
+}
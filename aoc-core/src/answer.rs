@@ -0,0 +1,60 @@
+use crate::json::ToJson;
+use std::fmt;
+
+/// The answer to one part of a puzzle: most are numbers, a few are text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+macro_rules! numbers {
+    ($($number:ty),*) => {
+        $(impl From<$number> for Answer {
+            fn from(number: $number) -> Self {
+                Answer::Number(number as i128)
+            }
+        })*
+    };
+}
+
+numbers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+/// Numbers are written as JSON numbers, and text as JSON strings.
+impl ToJson for Answer {
+    fn write_json(&self, out: &mut String) {
+        match self {
+            Answer::Number(number) => number.write_json(out),
+            Answer::Text(text) => text.write_json(out),
+        }
+    }
+}
+
+#[test]
+fn formats() {
+    assert_eq!(Answer::from(21800916620u64).to_json(), "21800916620");
+    assert_eq!(Answer::from(-3isize).to_string(), "-3");
+    assert_eq!(Answer::from("CMZ").to_json(), "\"CMZ\"");
+    assert_eq!(Answer::from("#.\n.#".to_string()).to_string(), "#.\n.#");
+}
@@ -0,0 +1,211 @@
+use std::{fmt, io};
+
+/// A problem with the contents of a puzzle input, pointing at where it was found.
+#[derive(Debug)]
+pub struct ParseError {
+    message: String,
+    file: Option<String>,
+    /// 1-based.
+    line: usize,
+    /// 1-based, in characters.
+    column: usize,
+    /// The offending line, without its line ending.
+    excerpt: String,
+}
+
+/// Anything that can go wrong while solving a puzzle.
+pub enum Error {
+    Io(io::Error),
+    Parse(ParseError),
+    /// The input is fine, but the answer, or a value on the way to it, is too large for its type.
+    Overflow(String),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl ParseError {
+    /// An error at the given (1-based) `line` and `column`, where `excerpt` is the text of that
+    /// line.
+    pub fn new(
+        message: impl Into<String>,
+        line: usize,
+        column: usize,
+        excerpt: impl Into<String>,
+    ) -> Self {
+        let excerpt: String = excerpt.into();
+        ParseError {
+            message: message.into(),
+            file: None,
+            line,
+            column,
+            excerpt: excerpt.trim_end_matches(['\r', '\n']).to_string(),
+        }
+    }
+
+    /// An error at the given byte `offset` into the whole of the `input`.
+    pub fn at_offset(message: impl Into<String>, input: &str, offset: usize) -> Self {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = input[offset..]
+            .find('\n')
+            .map(|i| offset + i)
+            .unwrap_or(input.len());
+        let line = before.matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+        Self::new(message, line, column, &input[line_start..line_end])
+    }
+
+    /// An error just past the end of the `input`, for when something is missing altogether.
+    pub fn at_end(message: impl Into<String>, input: &str) -> Self {
+        // Point at the end of the last line, rather than at an empty line after it.
+        let trimmed = input.trim_end_matches(['\r', '\n']);
+        Self::at_offset(message, trimmed, trimmed.len())
+    }
+
+    /// Names the file the input was read from.
+    pub fn in_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn excerpt(&self) -> &str {
+        &self.excerpt
+    }
+}
+
+impl fmt::Display for ParseError {
+    /// Renders the error much like `rustc` would:
+    ///
+    /// ```text
+    /// unexpected character 'x'
+    ///  --> input:3:3
+    ///   |
+    /// 3 | A x
+    ///   |   ^
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{}", self.message)?;
+        writeln!(
+            f,
+            "{}--> {}:{}:{}",
+            gutter,
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.excerpt)?;
+        write!(
+            f,
+            "{} | {}^",
+            gutter,
+            " ".repeat(self.column.saturating_sub(1))
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Error {
+    /// Names the file the input was read from, if this is a problem with its contents.
+    pub fn in_file(self, file: impl Into<String>) -> Self {
+        match self {
+            Error::Parse(e) => Error::Parse(e.in_file(file)),
+            e => e,
+        }
+    }
+
+    pub fn as_parse(&self) -> Option<&ParseError> {
+        match self {
+            Error::Parse(e) => Some(e),
+            Error::Io(_) | Error::Overflow(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => e.fmt(f),
+            Error::Parse(e) => e.fmt(f),
+            Error::Overflow(message) => f.write_str(message),
+        }
+    }
+}
+
+impl fmt::Debug for Error {
+    // Returning an `Error` from `main` prints it with `Debug`, where we'd rather have the report.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Parse(e) => Some(e),
+            Error::Overflow(_) => None,
+        }
+    }
+}
+
+/// Shorthand for naming the input file of a whole `Result`.
+pub trait InFile {
+    fn in_file(self, file: impl Into<String>) -> Self;
+}
+
+impl<T> InFile for Result<T> {
+    fn in_file(self, file: impl Into<String>) -> Self {
+        self.map_err(|e| e.in_file(file))
+    }
+}
+
+#[test]
+fn offsets() {
+    let input = "A Y\nB X\nC ?\n";
+    let error = ParseError::at_offset("unexpected character '?'", input, 10);
+    assert_eq!((error.line(), error.column()), (3, 3));
+    assert_eq!(error.excerpt(), "C ?");
+
+    let error = ParseError::at_end("missing something", input);
+    assert_eq!((error.line(), error.column()), (3, 4));
+}
+
+#[test]
+fn report() {
+    let error = ParseError::new("unexpected character '?'", 12, 3, "C ?\r\n").in_file("input");
+    assert_eq!(
+        error.to_string(),
+        "unexpected character '?'\n  --> input:12:3\n   |\n12 | C ?\n   |   ^"
+    );
+}
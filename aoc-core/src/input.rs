@@ -0,0 +1,104 @@
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// The environment variable naming the input file, when it isn't given as an argument.
+pub const INPUT_VAR: &str = "AOC_INPUT";
+
+/// A puzzle input, read in full so that both parts can be solved over it.
+pub struct Input {
+    name: String,
+    contents: String,
+}
+
+impl Input {
+    /// Reads the input named by the first command-line argument (other than options, like
+    /// `--format json`), or else by the `AOC_INPUT` environment variable, or else the `default`
+    /// path. A name of `-` stands for stdin.
+    pub fn from_args(default: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read(Self::source_from_args(default))
+    }
+
+    /// The path `from_args` reads the input from, for reading it some other way (e.g. a bit at a
+    /// time).
+    pub fn source_from_args(default: impl AsRef<Path>) -> PathBuf {
+        choose_source(
+            positional(env::args_os().skip(1)),
+            env::var_os(INPUT_VAR),
+            default.as_ref(),
+        )
+        .into()
+    }
+
+    /// Reads the input from the file at `source`, or from stdin if `source` is `-`.
+    pub fn read(source: impl AsRef<Path>) -> io::Result<Self> {
+        let source = source.as_ref();
+        if source == Path::new("-") {
+            let mut contents = String::new();
+            io::stdin().lock().read_to_string(&mut contents)?;
+            return Ok(Input {
+                name: "<stdin>".to_string(),
+                contents,
+            });
+        }
+        let contents = fs::read_to_string(source).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("could not read {}: {}", source.display(), e),
+            )
+        })?;
+        Ok(Input {
+            name: source.display().to_string(),
+            contents,
+        })
+    }
+
+    /// Where the input was read from, for error reports.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn contents(&self) -> &str {
+        &self.contents
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.contents.as_bytes()
+    }
+}
+
+/// The first argument that is neither an option nor an option's value.
+fn positional(mut args: impl Iterator<Item = OsString>) -> Option<OsString> {
+    while let Some(arg) = args.next() {
+        if arg.to_str().is_some_and(|arg| arg.starts_with("--")) {
+            args.next();
+            continue;
+        }
+        return Some(arg);
+    }
+    None
+}
+
+fn choose_source(arg: Option<OsString>, var: Option<OsString>, default: &Path) -> OsString {
+    arg.or(var.filter(|v| !v.is_empty()))
+        .unwrap_or_else(|| default.into())
+}
+
+#[test]
+fn source_precedence() {
+    let default = Path::new("day1/input");
+    let some = |s: &str| Some(OsString::from(s));
+    assert_eq!(choose_source(some("-"), some("var"), default), "-");
+    assert_eq!(choose_source(None, some("var"), default), "var");
+    assert_eq!(choose_source(None, some(""), default), "day1/input");
+    assert_eq!(choose_source(None, None, default), "day1/input");
+
+    let args = |args: &[&str]| positional(args.iter().map(OsString::from));
+    assert_eq!(args(&["--format", "json", "-"]), some("-"));
+    assert_eq!(args(&["path", "--format", "json"]), some("path"));
+    assert_eq!(args(&["--format", "json"]), None);
+}
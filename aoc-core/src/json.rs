@@ -0,0 +1,152 @@
+//! Just enough JSON to write reports for scripts to read, without pulling in a dependency.
+
+use std::fmt::{self, Display, Write};
+
+/// Something that can be written out as a JSON value.
+pub trait ToJson {
+    fn write_json(&self, out: &mut String);
+
+    fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+}
+
+macro_rules! numbers {
+    ($($number:ty),*) => {
+        $(impl ToJson for $number {
+            fn write_json(&self, out: &mut String) {
+                write!(out, "{}", self).unwrap();
+            }
+        })*
+    };
+}
+
+numbers!(u8, u32, u64, u128, usize, i32, i64, i128, isize);
+
+impl ToJson for f64 {
+    fn write_json(&self, out: &mut String) {
+        // JSON has no infinities or NaN.
+        if self.is_finite() {
+            write!(out, "{}", self).unwrap();
+        } else {
+            out.push_str("null");
+        }
+    }
+}
+
+impl ToJson for bool {
+    fn write_json(&self, out: &mut String) {
+        out.push_str(if *self { "true" } else { "false" });
+    }
+}
+
+impl ToJson for str {
+    fn write_json(&self, out: &mut String) {
+        out.push('"');
+        for c in self.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c < ' ' => write!(out, "\\u{:04x}", c as u32).unwrap(),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+    }
+}
+
+impl ToJson for String {
+    fn write_json(&self, out: &mut String) {
+        self.as_str().write_json(out);
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn write_json(&self, out: &mut String) {
+        (**self).write_json(out);
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn write_json(&self, out: &mut String) {
+        match self {
+            Some(value) => value.write_json(out),
+            None => out.push_str("null"),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn write_json(&self, out: &mut String) {
+        out.push('[');
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            value.write_json(out);
+        }
+        out.push(']');
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn write_json(&self, out: &mut String) {
+        self.as_slice().write_json(out);
+    }
+}
+
+/// A JSON object, with its fields in the order they were added.
+#[derive(Debug, Clone, Default)]
+pub struct Object {
+    fields: String,
+}
+
+impl Object {
+    pub fn new() -> Self {
+        Object::default()
+    }
+
+    pub fn field(mut self, key: &str, value: impl ToJson) -> Self {
+        if !self.fields.is_empty() {
+            self.fields.push(',');
+        }
+        key.write_json(&mut self.fields);
+        self.fields.push(':');
+        value.write_json(&mut self.fields);
+        self
+    }
+}
+
+impl ToJson for Object {
+    fn write_json(&self, out: &mut String) {
+        out.push('{');
+        out.push_str(&self.fields);
+        out.push('}');
+    }
+}
+
+impl Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_json())
+    }
+}
+
+#[test]
+fn objects() {
+    let object = Object::new()
+        .field("day", 5)
+        .field("answer", "#.\n\"x\"\u{1}")
+        .field("elapsed", 0.5)
+        .field("parts", vec![1u8, 2])
+        .field("missing", None::<u8>);
+    assert_eq!(
+        object.to_string(),
+        r##"{"day":5,"answer":"#.\n\"x\"\u0001","elapsed":0.5,"parts":[1,2],"missing":null}"##
+    );
+    assert_eq!(Object::new().to_string(), "{}");
+}
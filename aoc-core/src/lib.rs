@@ -0,0 +1,14 @@
+//! Bits and pieces shared by every day's solutions.
+
+pub mod answer;
+pub mod error;
+pub mod input;
+pub mod json;
+pub mod report;
+pub mod rng;
+
+pub use answer::Answer;
+pub use error::{Error, InFile, ParseError, Result};
+pub use input::Input;
+pub use report::{Format, Report};
+pub use rng::Rng;
@@ -0,0 +1,95 @@
+//! How a day's answers are printed: as sentences for people to read, or as JSON for scripts.
+
+use crate::{json::Object, Answer, Result};
+use std::{
+    env, io,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    /// A JSON object per answer, one per line.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("{} is not a format (text or json)", format)),
+        }
+    }
+}
+
+/// Prints the answers of one day's puzzle.
+pub struct Report {
+    day: u8,
+    format: Format,
+}
+
+impl Report {
+    pub fn new(day: u8, format: Format) -> Self {
+        Report { day, format }
+    }
+
+    /// A report in the format given by the `--format` argument, if any.
+    pub fn from_args(day: u8) -> io::Result<Self> {
+        let mut args = env::args().skip(1);
+        let mut format = Format::Text;
+        while let Some(arg) = args.next() {
+            if arg == "--format" {
+                let value = args.next().unwrap_or_default();
+                format = value
+                    .parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            }
+        }
+        Ok(Report::new(day, format))
+    }
+
+    /// Solves a part, timing how long it takes, and prints the answer. As text, the answer
+    /// takes the place of the `{}` in `sentence`.
+    pub fn part<T: Into<Answer>>(
+        &self,
+        part: u8,
+        sentence: &str,
+        solve: impl FnOnce() -> Result<T>,
+    ) -> Result<()> {
+        let start = Instant::now();
+        let answer = solve()?.into();
+        let elapsed = start.elapsed();
+        match self.format {
+            Format::Text => println!("{}", sentence.replacen("{}", &answer.to_string(), 1)),
+            Format::Json => println!("{}", answer_json(self.day, part, &answer, elapsed)),
+        }
+        Ok(())
+    }
+}
+
+/// An answer as JSON, with how long it took in seconds.
+pub fn answer_json(day: u8, part: u8, answer: &Answer, elapsed: Duration) -> Object {
+    Object::new()
+        .field("day", day)
+        .field("part", part)
+        .field("answer", answer)
+        .field("elapsed", elapsed.as_secs_f64())
+}
+
+#[test]
+fn json_answers() {
+    let elapsed = Duration::from_millis(1500);
+    assert_eq!(
+        answer_json(5, 1, &"CMZ".into(), elapsed).to_string(),
+        r#"{"day":5,"part":1,"answer":"CMZ","elapsed":1.5}"#
+    );
+    assert_eq!(
+        answer_json(1, 2, &45000u32.into(), elapsed).to_string(),
+        r#"{"day":1,"part":2,"answer":45000,"elapsed":1.5}"#
+    );
+}
@@ -0,0 +1,85 @@
+//! A small, seeded pseudo-random number generator (SplitMix64), for generating puzzle inputs
+//! that can be reproduced from their seed.
+
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, which must not be empty.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "expected a non-empty range");
+        // Reject the values that would make the lowest results more likely.
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+
+    /// A number in `low..=high`.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low <= high, "expected a non-empty range");
+        match (high - low).checked_add(1) {
+            Some(n) => low + self.below(n),
+            None => self.next_u64(),
+        }
+    }
+
+    /// An index into something of length `len`, which must not be zero.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// True one time in `n`, on average.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[test]
+fn reproducible() {
+    let mut a = Rng::new(7);
+    let mut b = Rng::new(7);
+    let mut c = Rng::new(8);
+    let from_a: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+    assert_eq!(from_a, (0..8).map(|_| b.next_u64()).collect::<Vec<_>>());
+    assert_ne!(from_a, (0..8).map(|_| c.next_u64()).collect::<Vec<_>>());
+
+    for _ in 0..1000 {
+        assert!((3..=5).contains(&a.between(3, 5)));
+        assert!(a.below(10) < 10);
+    }
+    assert_eq!(a.between(4, 4), 4);
+    a.between(0, u64::MAX);
+
+    let mut items = [1, 2, 3, 4, 5];
+    a.shuffle(&mut items);
+    items.sort();
+    assert_eq!(items, [1, 2, 3, 4, 5]);
+}
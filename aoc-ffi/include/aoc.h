@@ -0,0 +1,48 @@
+/* Generated by aoc-ffi's header() in aoc-ffi/src/lib.rs; do not edit.
+ * Regenerate with: AOC_UPDATE_HEADER=1 cargo test -p aoc-ffi */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The answer was written to the buffer. */
+#define AOC_OK 0
+/* The day has not been solved. */
+#define AOC_NO_SUCH_DAY 1
+/* The part is not 1 or 2. */
+#define AOC_NO_SUCH_PART 2
+/* The input is not UTF-8. */
+#define AOC_INVALID_UTF8 3
+/* The input is malformed; the error was written to the buffer. */
+#define AOC_BAD_INPUT 4
+/* The buffer is too small; *out_len was set to the size needed. */
+#define AOC_BUFFER_TOO_SMALL 5
+/* A pointer that was needed is null. */
+#define AOC_NULL_POINTER 6
+/* The solver panicked; the panic was written to the buffer. */
+#define AOC_PANIC 7
+
+/* Solves one part of a day's puzzle over the input_len bytes at input.
+ *
+ * The answer (or, when the input is malformed, the error) is written to out as a
+ * NUL-terminated string, and *out_len is set to its length without the NUL. *out_len must hold
+ * the size of out on the way in; if that's too small, nothing is written, *out_len is set to
+ * the size needed, and AOC_BUFFER_TOO_SMALL is returned. out may be NULL if *out_len is 0, to
+ * ask for the size. */
+int32_t aoc_solve(uint32_t day, uint32_t part, const uint8_t *input, size_t input_len,
+                  char *out, size_t *out_len);
+
+/* What a status returned by aoc_solve means, as a static string. */
+const char *aoc_status_message(int32_t status);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC_H */
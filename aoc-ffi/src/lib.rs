@@ -0,0 +1,231 @@
+//! The solvers, callable from C (or from anything with a C FFI) through `include/aoc.h`, which
+//! is generated from this file by `header`.
+
+use aoc::Day;
+use std::{ffi::c_char, panic, ptr, slice, str};
+
+macro_rules! statuses {
+    ($($name:ident = $value:literal, $description:literal;)*) => {
+        $(
+            #[doc = $description]
+            pub const $name: i32 = $value;
+        )*
+
+        /// Every status `aoc_solve` can return: its name in C, its value, and what it means (with
+        /// a NUL on the end, for C).
+        pub const STATUSES: &[(&str, i32, &str)] =
+            &[$((stringify!($name), $value, concat!($description, "\0"))),*];
+    };
+}
+
+statuses! {
+    AOC_OK = 0, "The answer was written to the buffer.";
+    AOC_NO_SUCH_DAY = 1, "The day has not been solved.";
+    AOC_NO_SUCH_PART = 2, "The part is not 1 or 2.";
+    AOC_INVALID_UTF8 = 3, "The input is not UTF-8.";
+    AOC_BAD_INPUT = 4, "The input is malformed; the error was written to the buffer.";
+    AOC_BUFFER_TOO_SMALL = 5, "The buffer is too small; *out_len was set to the size needed.";
+    AOC_NULL_POINTER = 6, "A pointer that was needed is null.";
+    AOC_PANIC = 7, "The solver panicked; the panic was written to the buffer.";
+}
+
+/// Solves one part of a day's puzzle over the `input_len` bytes at `input`.
+///
+/// The answer (or, when the input is malformed, the error) is written to `out` as a
+/// NUL-terminated string, and `*out_len` is set to its length without the NUL. `*out_len` must
+/// hold the size of `out` on the way in; if that's too small, nothing is written, `*out_len` is
+/// set to the size needed, and `AOC_BUFFER_TOO_SMALL` is returned. `out` may be null if
+/// `*out_len` is zero, to ask for the size.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes (or may be null if `input_len` is zero),
+/// `out_len` must point to a writable `size_t`, and `out` to `*out_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input: *const u8,
+    input_len: usize,
+    out: *mut c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if out_len.is_null() || (input.is_null() && input_len > 0) || (out.is_null() && *out_len > 0) {
+        return AOC_NULL_POINTER;
+    }
+    let day = match u8::try_from(day).ok().and_then(Day::get) {
+        Some(day) => day,
+        None => return AOC_NO_SUCH_DAY,
+    };
+    let part = match part {
+        1 | 2 => part as u8,
+        _ => return AOC_NO_SUCH_PART,
+    };
+    let input = match input_len {
+        0 => &[],
+        _ => slice::from_raw_parts(input, input_len),
+    };
+    let input = match str::from_utf8(input) {
+        Ok(input) => input,
+        Err(_) => return AOC_INVALID_UTF8,
+    };
+
+    // Unwinding into C is undefined behaviour, so a panic has to stop here.
+    let solved = panic::catch_unwind(|| day.solve(part, input).unwrap());
+    let (status, text) = match solved {
+        Ok(Ok(answer)) => (AOC_OK, answer.to_string()),
+        Ok(Err(e)) => (AOC_BAD_INPUT, e.to_string()),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| panic.downcast_ref::<&str>().copied())
+                .unwrap_or("(no message)");
+            (AOC_PANIC, format!("the solver panicked: {}", message))
+        }
+    };
+
+    if *out_len <= text.len() {
+        *out_len = text.len() + 1;
+        return AOC_BUFFER_TOO_SMALL;
+    }
+    ptr::copy_nonoverlapping(text.as_ptr(), out.cast(), text.len());
+    *out.add(text.len()) = 0;
+    *out_len = text.len();
+    status
+}
+
+/// What a status returned by `aoc_solve` means, as a static NUL-terminated string.
+#[no_mangle]
+pub extern "C" fn aoc_status_message(status: i32) -> *const c_char {
+    STATUSES
+        .iter()
+        .find(|&&(_, value, _)| value == status)
+        .map_or("Unknown status.\0", |&(_, _, description)| description)
+        .as_ptr()
+        .cast()
+}
+
+/// The contents of `include/aoc.h`.
+pub fn header() -> String {
+    let mut statuses = String::new();
+    for (name, value, description) in STATUSES {
+        let description = description.trim_end_matches('\0');
+        statuses.push_str(&format!(
+            "/* {} */\n#define {} {}\n",
+            description, name, value
+        ));
+    }
+    format!(
+        "\
+/* Generated by aoc-ffi's header() in aoc-ffi/src/lib.rs; do not edit.
+ * Regenerate with: AOC_UPDATE_HEADER=1 cargo test -p aoc-ffi */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern \"C\" {{
+#endif
+
+{}
+/* Solves one part of a day's puzzle over the input_len bytes at input.
+ *
+ * The answer (or, when the input is malformed, the error) is written to out as a
+ * NUL-terminated string, and *out_len is set to its length without the NUL. *out_len must hold
+ * the size of out on the way in; if that's too small, nothing is written, *out_len is set to
+ * the size needed, and AOC_BUFFER_TOO_SMALL is returned. out may be NULL if *out_len is 0, to
+ * ask for the size. */
+int32_t aoc_solve(uint32_t day, uint32_t part, const uint8_t *input, size_t input_len,
+                  char *out, size_t *out_len);
+
+/* What a status returned by aoc_solve means, as a static string. */
+const char *aoc_status_message(int32_t status);
+
+#ifdef __cplusplus
+}}
+#endif
+
+#endif /* AOC_H */
+",
+        statuses
+    )
+}
+
+#[cfg(test)]
+use std::ffi::CStr;
+
+#[cfg(test)]
+fn solve(day: u32, part: u32, input: &str, capacity: usize) -> (i32, String, usize) {
+    let mut out = vec![0u8; capacity];
+    let mut out_len = capacity;
+    let out_ptr = if capacity == 0 {
+        ptr::null_mut()
+    } else {
+        out.as_mut_ptr().cast()
+    };
+    let status = unsafe {
+        aoc_solve(
+            day,
+            part,
+            input.as_ptr(),
+            input.len(),
+            out_ptr,
+            &mut out_len,
+        )
+    };
+    let text = CStr::from_bytes_until_nul(&out)
+        .map(|text| text.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    (status, text, out_len)
+}
+
+#[test]
+fn solving() {
+    assert_eq!(
+        solve(2, 1, "A Y\nB X\nC Z\n", 16),
+        (AOC_OK, "15".to_string(), 2)
+    );
+    assert_eq!(
+        solve(2, 1, "A Y\n", 0),
+        (AOC_BUFFER_TOO_SMALL, String::new(), 2)
+    );
+    assert_eq!(solve(2, 1, "A Y\n", 1).0, AOC_BUFFER_TOO_SMALL);
+    assert_eq!(solve(99, 1, "", 16).0, AOC_NO_SUCH_DAY);
+    assert_eq!(solve(2, 3, "", 16).0, AOC_NO_SUCH_PART);
+
+    let (status, error, _) = solve(2, 1, "A Y\nB Q\n", 256);
+    assert_eq!(status, AOC_BAD_INPUT);
+    assert!(
+        error.starts_with("expected X, Y or Z, found 'Q'"),
+        "{}",
+        error
+    );
+
+    let mut out_len = 0;
+    let status = unsafe { aoc_solve(2, 1, ptr::null(), 4, ptr::null_mut(), &mut out_len) };
+    assert_eq!(status, AOC_NULL_POINTER);
+}
+
+#[test]
+fn status_messages() {
+    let message = |status| unsafe { CStr::from_ptr(aoc_status_message(status)) };
+    assert_eq!(message(AOC_NO_SUCH_PART), c"The part is not 1 or 2.");
+    assert_eq!(message(-1), c"Unknown status.");
+}
+
+#[test]
+fn header_is_up_to_date() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/include/aoc.h");
+    if std::env::var_os("AOC_UPDATE_HEADER").is_some() {
+        std::fs::write(path, header()).unwrap();
+    }
+    let checked_in = std::fs::read_to_string(path).unwrap_or_default();
+    assert!(
+        checked_in == header(),
+        "include/aoc.h is out of date; regenerate it with AOC_UPDATE_HEADER=1 cargo test -p aoc-ffi"
+    );
+}
@@ -0,0 +1,34 @@
+//! Builds `solve.c` against `include/aoc.h` and the shared library, and runs it.
+
+use std::{env, path::PathBuf, process::Command};
+
+#[test]
+fn c_program() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // The test runs from `target/<profile>/deps`, where the library is built for it.
+    let exe = env::current_exe().unwrap();
+    let lib_dir = exe.parent().unwrap();
+    let program = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("solve");
+
+    let cc = env::var_os("CC").unwrap_or_else(|| "cc".into());
+    let compiled = Command::new(cc)
+        .arg(manifest_dir.join("tests/solve.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .args(["-laoc_ffi", "-Wall", "-Werror", "-o"])
+        .arg(&program)
+        .status()
+        .expect("a C compiler (cc, or $CC)");
+    assert!(compiled.success(), "solve.c didn't compile");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "solve.c failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
@@ -0,0 +1,53 @@
+/* Calls the solvers through aoc.h, as a C program would. Built and run by tests/c.rs. */
+
+#include "aoc.h"
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static int failures = 0;
+
+static void check(int ok, const char *what) {
+    if (!ok) {
+        fprintf(stderr, "FAILED: %s\n", what);
+        failures++;
+    }
+}
+
+int main(void) {
+    const char *input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+    char out[256];
+    size_t out_len = sizeof out;
+
+    int32_t status = aoc_solve(1, 1, (const uint8_t *)input, strlen(input), out, &out_len);
+    check(status == AOC_OK, "day 1, part 1 is solved");
+    check(strcmp(out, "24000") == 0 && out_len == 5, "day 1, part 1 is 24000");
+
+    /* Asking for the size first, then solving into a buffer of that size. */
+    out_len = 0;
+    status = aoc_solve(1, 2, (const uint8_t *)input, strlen(input), NULL, &out_len);
+    check(status == AOC_BUFFER_TOO_SMALL && out_len == 6, "the size needed is given");
+    char *answer = malloc(out_len);
+    status = aoc_solve(1, 2, (const uint8_t *)input, strlen(input), answer, &out_len);
+    check(status == AOC_OK && strcmp(answer, "45000") == 0, "day 1, part 2 is 45000");
+    free(answer);
+
+    const char *malformed = "A Y\nB Q\n";
+    out_len = sizeof out;
+    status = aoc_solve(2, 1, (const uint8_t *)malformed, strlen(malformed), out, &out_len);
+    check(status == AOC_BAD_INPUT, "malformed input is reported");
+    check(strncmp(out, "expected X, Y or Z", 18) == 0, "the error is written out");
+
+    out_len = sizeof out;
+    check(aoc_solve(99, 1, NULL, 0, out, &out_len) == AOC_NO_SUCH_DAY, "day 99 is unsolved");
+    check(aoc_solve(1, 3, NULL, 0, out, &out_len) == AOC_NO_SUCH_PART, "there's no part 3");
+    check(aoc_solve(1, 1, NULL, 0, out, NULL) == AOC_NULL_POINTER, "out_len is needed");
+    check(strcmp(aoc_status_message(AOC_NO_SUCH_DAY), "The day has not been solved.") == 0,
+          "statuses have messages");
+
+    if (failures == 0) {
+        printf("ok\n");
+    }
+    return failures == 0 ? 0 : 1;
+}
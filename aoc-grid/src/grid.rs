@@ -0,0 +1,201 @@
+use crate::{Direction, Point};
+use aoc_core::ParseError;
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parses a grid with a cell per character, and a row per line. `cell` is told where each
+    /// character is, and may reject it with a message, which is reported at that character.
+    pub fn parse<F>(input: &str, mut cell: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(Point, char) -> Result<T, String>,
+    {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let error = |message: String, x: usize| ParseError::new(message, y + 1, x + 1, line);
+            let mut row_width = 0;
+            for (x, c) in line.chars().enumerate() {
+                let point = Point::new(x as isize, y as isize);
+                cells.push(cell(point, c).map_err(|message| error(message, x))?);
+                row_width += 1;
+            }
+            match width {
+                Some(width) if width != row_width => {
+                    let message =
+                        format!("expected {} cells in this row, found {}", width, row_width);
+                    return Err(error(message, row_width.min(width)));
+                }
+                Some(_) => {}
+                None => width = Some(row_width),
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                cells,
+                width,
+                height,
+            }),
+            _ => Err(ParseError::new("expected a grid", 1, 1, "")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(point.y).ok().filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn point_of(&self, index: usize) -> Point {
+        Point::new((index % self.width) as isize, (index / self.width) as isize)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.cells.len()).map(|i| self.point_of(i))
+    }
+
+    /// Every cell in the grid along with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.point_of(i), cell))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The cells met walking from `from` (exclusive) in `direction` until leaving the grid.
+    pub fn ray(&self, from: Point, direction: Direction) -> impl Iterator<Item = (Point, &T)> {
+        let mut point = from;
+        std::iter::from_fn(move || {
+            point += direction;
+            self.get(point).map(|cell| (point, cell))
+        })
+    }
+
+    /// The points in the grid sharing a side with `point`.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbours4().filter(|&p| self.contains(p))
+    }
+
+    /// The points in the grid sharing a side or a corner with `point`.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbours8().filter(|&p| self.contains(p))
+    }
+
+    /// Draws the grid with a character per cell, and a line per row.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut rendered = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            if !rendered.is_empty() {
+                rendered.push('\n');
+            }
+            rendered.extend(row.iter().map(&mut cell));
+        }
+        rendered
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is out of bounds", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is out of bounds", point))
+    }
+}
+
+#[test]
+fn parse_and_render() {
+    let grid = Grid::parse("123\n456\n", |_, c| Ok(c)).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[Point::new(2, 1)], '6');
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(grid.get(Point::new(0, -1)), None);
+    assert_eq!(grid.column(1).collect::<String>(), "25");
+    assert_eq!(grid.row(1), ['4', '5', '6']);
+    assert_eq!(grid.render(|&c| c), "123\n456");
+
+    let error = Grid::parse("123\n45\n", |_, c| Ok(c)).unwrap_err();
+    assert_eq!((error.line(), error.column()), (2, 3));
+    let error = Grid::parse("123\n4x6\n", |_, c| match c {
+        'x' => Err("not a digit".to_string()),
+        c => Ok(c),
+    })
+    .unwrap_err();
+    assert_eq!((error.line(), error.column()), (2, 2));
+}
+
+#[test]
+fn rays_and_neighbours() {
+    let grid = Grid::parse("abc\ndef\nghi", |_, c| Ok(c)).unwrap();
+    let ray: String = grid
+        .ray(Point::new(0, 1), Direction::Right)
+        .map(|(_, &c)| c)
+        .collect();
+    assert_eq!(ray, "ef");
+    assert_eq!(grid.ray(Point::new(1, 0), Direction::Up).count(), 0);
+    assert_eq!(grid.neighbours4(Point::new(0, 0)).count(), 2);
+    assert_eq!(grid.neighbours8(Point::new(0, 0)).count(), 3);
+    assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+}
@@ -0,0 +1,7 @@
+//! Dense two-dimensional grids, and the coordinates and directions to move around them.
+
+mod grid;
+mod point;
+
+pub use grid::Grid;
+pub use point::{Direction, Point};
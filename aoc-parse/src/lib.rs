@@ -0,0 +1,288 @@
+//! A small character-at-a-time parser, for the puzzles whose input is simple enough that a
+//! grammar would be overkill.
+//!
+//! The parser reads its input a line at a time, either from a `&str` or from anything that is
+//! `BufRead`, and keeps track of where it is, so that every error points at the offending line and
+//! column.
+
+use aoc_core::{Error, ParseError, Result};
+use std::{io, str::FromStr};
+
+/// Where the parser reads its lines from.
+pub trait Source {
+    /// Appends the next line, including its line ending, to `buf`. Returns `false` at the end of
+    /// the input.
+    fn next_line(&mut self, buf: &mut String) -> io::Result<bool>;
+}
+
+impl Source for &str {
+    fn next_line(&mut self, buf: &mut String) -> io::Result<bool> {
+        if self.is_empty() {
+            return Ok(false);
+        }
+        let end = self.find('\n').map(|i| i + 1).unwrap_or(self.len());
+        buf.push_str(&self[..end]);
+        *self = &self[end..];
+        Ok(true)
+    }
+}
+
+/// Reads lines from a buffered reader.
+pub struct Reader<R>(pub R);
+
+impl<R: io::BufRead> Source for Reader<R> {
+    fn next_line(&mut self, buf: &mut String) -> io::Result<bool> {
+        Ok(self.0.read_line(buf)? > 0)
+    }
+}
+
+/// A place in the input; both are 1-based, and the column counts characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+pub struct Parser<S: Source> {
+    source: S,
+    /// The line being read, with its line ending.
+    line: String,
+    /// The byte offset of the next character in `line`.
+    offset: usize,
+    position: Position,
+    /// Reading fails quietly, as the end of the input; the error is handed out by the next thing
+    /// that fails because of it.
+    io_error: Option<io::Error>,
+}
+
+impl<R: io::BufRead> Parser<Reader<R>> {
+    pub fn from_reader(input: R) -> Self {
+        Parser::new(Reader(input))
+    }
+}
+
+impl<S: Source> Parser<S> {
+    /// A parser over a `&str`, or any other `Source`.
+    pub fn new(source: S) -> Self {
+        Parser {
+            source,
+            line: String::new(),
+            offset: 0,
+            position: Position { line: 0, column: 1 },
+            io_error: None,
+        }
+    }
+
+    /// Where the next character is.
+    pub fn position(&mut self) -> Position {
+        self.peek();
+        self.position
+    }
+
+    /// An error at the next character, unless reading the input failed, in which case that's the
+    /// error.
+    pub fn error(&mut self, message: impl Into<String>) -> Error {
+        let position = self.position();
+        self.error_at(message, position)
+    }
+
+    /// An error at an earlier `position`, which is expected to be on the line being read.
+    pub fn error_at(&mut self, message: impl Into<String>, position: Position) -> Error {
+        if let Some(e) = self.io_error.take() {
+            return e.into();
+        }
+        let excerpt = if position.line == self.position.line {
+            self.line.as_str()
+        } else {
+            ""
+        };
+        ParseError::new(message, position.line, position.column, excerpt).into()
+    }
+
+    pub fn peek(&mut self) -> Option<char> {
+        if self.offset == self.line.len() && self.io_error.is_none() {
+            let mut line = String::new();
+            match self.source.next_line(&mut line) {
+                Ok(true) => {
+                    self.line = line;
+                    self.offset = 0;
+                    self.position.line += 1;
+                    self.position.column = 1;
+                }
+                // Stay just past the end of the last line, so errors can still point at it.
+                Ok(false) => return None,
+                Err(e) => {
+                    self.io_error = Some(e);
+                    return None;
+                }
+            }
+        }
+        self.line[self.offset..].chars().next()
+    }
+
+    /// Consumes the next character.
+    pub fn take(&mut self) -> Option<char> {
+        let next = self.peek()?;
+        self.offset += next.len_utf8();
+        self.position.column += 1;
+        Some(next)
+    }
+
+    pub fn eof(&mut self) -> bool {
+        self.peek().is_none()
+    }
+
+    /// Like `eof`, but fails if the input ended because reading it failed.
+    pub fn done(&mut self) -> Result<bool> {
+        let eof = self.eof();
+        match self.io_error.take() {
+            Some(e) => Err(e.into()),
+            None => Ok(eof),
+        }
+    }
+
+    fn describe_next(&mut self) -> String {
+        match self.peek() {
+            Some('\n') | Some('\r') => "the end of the line".to_string(),
+            Some(c) => format!("{:?}", c),
+            None => "the end of the input".to_string(),
+        }
+    }
+
+    pub fn match_(&mut self, to_match: char) -> Result<()> {
+        if self.try_match(to_match) {
+            return Ok(());
+        }
+        let found = self.describe_next();
+        Err(self.error(format!("expected {:?}, found {}", to_match, found)))
+    }
+
+    pub fn try_match(&mut self, to_match: char) -> bool {
+        self.try_match_predicate(|c| c == to_match).is_some()
+    }
+
+    pub fn match_str(&mut self, to_match: &str) -> Result<()> {
+        for c in to_match.chars() {
+            if !self.try_match(c) {
+                let found = self.describe_next();
+                return Err(self.error(format!("expected {:?}, found {}", to_match, found)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes the next character if it satisfies the `predicate`.
+    pub fn try_match_predicate(&mut self, predicate: impl FnOnce(char) -> bool) -> Option<char> {
+        match self.peek() {
+            Some(c) if predicate(c) => self.take(),
+            _ => None,
+        }
+    }
+
+    /// Consumes characters for as long as they satisfy the `predicate`, returning how many.
+    pub fn eat_predicate(&mut self, mut predicate: impl FnMut(char) -> bool) -> usize {
+        let mut eaten = 0;
+        while self.try_match_predicate(&mut predicate).is_some() {
+            eaten += 1;
+        }
+        eaten
+    }
+
+    pub fn take_while(&mut self, mut predicate: impl FnMut(char) -> bool) -> String {
+        let mut taken = String::new();
+        while let Some(c) = self.try_match_predicate(&mut predicate) {
+            taken.push(c);
+        }
+        taken
+    }
+
+    /// Skips whitespace, across lines.
+    pub fn skip_whitespace(&mut self) {
+        self.eat_predicate(char::is_whitespace);
+    }
+
+    /// Skips spaces and tabs, staying on the same line.
+    pub fn skip_spaces(&mut self) {
+        self.eat_predicate(|c| c == ' ' || c == '\t');
+    }
+
+    /// Matches the end of a line, or of the whole input.
+    pub fn end_of_line(&mut self) -> Result<()> {
+        self.try_match('\r');
+        if self.try_match('\n') || self.eof() {
+            return Ok(());
+        }
+        let found = self.describe_next();
+        Err(self.error(format!("expected the end of the line, found {}", found)))
+    }
+
+    /// Parses a run of digits.
+    pub fn number<T: FromStr>(&mut self) -> Result<T> {
+        let start = self.position();
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        self.parse_number(digits, start)
+    }
+
+    /// Parses a run of digits, optionally preceded by a sign.
+    pub fn signed<T: FromStr>(&mut self) -> Result<T> {
+        let start = self.position();
+        let mut digits = String::new();
+        if let Some(sign) = self.try_match_predicate(|c| c == '-' || c == '+') {
+            digits.push(sign);
+        }
+        digits.push_str(&self.take_while(|c| c.is_ascii_digit()));
+        self.parse_number(digits, start)
+    }
+
+    fn parse_number<T: FromStr>(&mut self, digits: String, start: Position) -> Result<T> {
+        if !digits.ends_with(|c: char| c.is_ascii_digit()) {
+            let found = self.describe_next();
+            return Err(self.error(format!("expected a number, found {}", found)));
+        }
+        digits
+            .parse::<T>()
+            .map_err(|_| self.error_at("number is out of range", start))
+    }
+}
+
+#[test]
+fn positions() {
+    let mut parser = Parser::new("move 3\r\nfrom -12\n");
+    parser.match_str("move ").unwrap();
+    assert_eq!(parser.number::<u8>().unwrap(), 3);
+    parser.end_of_line().unwrap();
+    assert_eq!(parser.position(), Position { line: 2, column: 1 });
+    parser.match_str("from ").unwrap();
+    assert_eq!(parser.signed::<i32>().unwrap(), -12);
+    parser.end_of_line().unwrap();
+    assert!(parser.eof());
+}
+
+#[test]
+fn errors() {
+    let input = "1 2\n3 x\n300\n";
+    let mut parser = Parser::from_reader(io::BufReader::new(input.as_bytes()));
+    parser.take_while(|c| c != '\n');
+    parser.end_of_line().unwrap();
+    parser.number::<u8>().unwrap();
+    parser.match_(' ').unwrap();
+    let error = parser.number::<u8>().unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (2, 3));
+    assert_eq!(error.excerpt(), "3 x");
+    assert_eq!(error.message(), "expected a number, found 'x'");
+
+    parser.skip_whitespace();
+    parser.take();
+    parser.skip_whitespace();
+    let error = parser.number::<u8>().unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (3, 1));
+    assert_eq!(error.message(), "number is out of range");
+
+    parser.end_of_line().unwrap();
+    let error = parser.match_(',').unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (3, 5));
+    assert_eq!(error.excerpt(), "300");
+}
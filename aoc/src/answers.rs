@@ -0,0 +1,157 @@
+//! The known answers, checked in as an `answers` file next to each day's inputs.
+//!
+//! Each line holds the input file (relative to the day's directory), the part, and the answer,
+//! separated by whitespace; the answer runs to the end of the line, with line breaks written as
+//! `\n` (and backslashes as `\\`). Blank lines and lines starting with `#` are ignored:
+//!
+//! ```text
+//! # file       part  answer
+//! input        1     66306
+//! exampleinput 2     45000
+//! ```
+
+use crate::Day;
+use aoc_core::{InFile, Input, ParseError};
+use std::{fs, io, path::PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub file: String,
+    pub part: u8,
+    pub answer: String,
+}
+
+/// What came of solving a known input.
+pub struct Outcome {
+    pub expected: Expected,
+    pub got: aoc_core::Result<String>,
+}
+
+impl Outcome {
+    pub fn is_ok(&self) -> bool {
+        matches!(&self.got, Ok(answer) if *answer == self.expected.answer)
+    }
+}
+
+pub fn parse(contents: &str) -> Result<Vec<Expected>, ParseError> {
+    let mut expected = vec![];
+    for (line_no, line) in contents.lines().enumerate() {
+        let error = |message: &str, column| ParseError::new(message, line_no + 1, column, line);
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        // The columns borrow from `line`, so `column_of` can tell where they are.
+        let column_of = |s: &str| {
+            let offset = s.as_ptr() as usize - line.as_ptr() as usize;
+            line[..offset].chars().count() + 1
+        };
+        let (file, rest) = split(trimmed);
+        let (part, rest) = split(rest);
+        let part = match part {
+            "1" => 1,
+            "2" => 2,
+            _ => {
+                let message = "expected a part (1 or 2) after the file";
+                return Err(error(message, column_of(part)));
+            }
+        };
+        let answer = match rest.trim_end() {
+            "" => return Err(error("expected an answer after the part", column_of(rest))),
+            answer => unescape(answer),
+        };
+        expected.push(Expected {
+            file: file.to_string(),
+            part,
+            answer,
+        });
+    }
+    Ok(expected)
+}
+
+/// Splits off the first column, and the whitespace after it.
+fn split(s: &str) -> (&str, &str) {
+    let end = s.find(char::is_whitespace).unwrap_or(s.len());
+    (&s[..end], s[end..].trim_start())
+}
+
+fn unescape(answer: &str) -> String {
+    let mut unescaped = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            (c, _) => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+impl Day {
+    pub fn answers_file(&self) -> PathBuf {
+        self.dir().join("answers")
+    }
+
+    /// The known answers for this day; none if there's no `answers` file.
+    pub fn answers(&self) -> aoc_core::Result<Vec<Expected>> {
+        let path = self.answers_file();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+        parse(&contents).map_err(|e| e.in_file(path.display().to_string()).into())
+    }
+
+    /// Solves every input with a known answer.
+    pub fn verify(&self) -> aoc_core::Result<Vec<Outcome>> {
+        let mut outcomes = vec![];
+        for expected in self.answers()? {
+            let input = Input::read(self.dir().join(&expected.file))?;
+            let got = self
+                .solve(expected.part, input.contents())
+                .expect("the part to be 1 or 2")
+                .map(|answer| answer.to_string())
+                .in_file(input.name());
+            outcomes.push(Outcome { expected, got });
+        }
+        Ok(outcomes)
+    }
+}
+
+#[test]
+fn parsing() {
+    let contents = "# file part answer\n\ninput 1 66306\nexampleinput  2  #.\\n.#\\\\\n";
+    let expected = parse(contents).unwrap();
+    assert_eq!(expected.len(), 2);
+    assert_eq!((expected[0].file.as_str(), expected[0].part), ("input", 1));
+    assert_eq!(expected[0].answer, "66306");
+    assert_eq!(expected[1].answer, "#.\n.#\\");
+
+    let error = parse("input 1 2\ninput 3 4\n").unwrap_err();
+    assert_eq!((error.line(), error.column()), (2, 7));
+}
+
+#[test]
+fn known_answers() {
+    for day in crate::DAYS {
+        for outcome in day.verify().unwrap() {
+            assert!(
+                outcome.is_ok(),
+                "day {}, part {} of {}: expected {:?}, got {:?}",
+                day.number,
+                outcome.expected.part,
+                outcome.expected.file,
+                outcome.expected.answer,
+                outcome.got
+            );
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Timing the solvers: each stage runs a few times to warm up, then many times while measuring
+//! wall time and, if the `CountingAllocator` is the global allocator, allocations.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator, counting every allocation made through it. Declare it as the
+/// `#[global_allocator]` for `measure` to report allocations.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // A reallocation counts as a new allocation of the new size.
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// The number of allocations, and the bytes allocated, since the program started.
+fn allocated() -> (u64, u64) {
+    (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        BYTES.load(Ordering::Relaxed),
+    )
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// Runs before measuring, to warm up the caches.
+    pub warmup: usize,
+    /// Runs measured.
+    pub iterations: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            warmup: 3,
+            iterations: 20,
+        }
+    }
+}
+
+/// How long a stage took, and how much it allocated, over every measured run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    /// Allocations per run, on average.
+    pub allocations: u64,
+    /// Bytes allocated per run, on average.
+    pub bytes: u64,
+}
+
+/// Runs `stage` as `options` ask, stopping at the first error.
+pub fn measure<T>(
+    options: Options,
+    mut stage: impl FnMut() -> aoc_core::Result<T>,
+) -> aoc_core::Result<Stats> {
+    assert!(options.iterations > 0, "expected at least one iteration");
+    for _ in 0..options.warmup {
+        black_box(stage()?);
+    }
+
+    let mut times = Vec::with_capacity(options.iterations);
+    let (allocations_before, bytes_before) = allocated();
+    for _ in 0..options.iterations {
+        let start = Instant::now();
+        let result = stage();
+        times.push(start.elapsed());
+        black_box(result?);
+    }
+    let (allocations_after, bytes_after) = allocated();
+
+    times.sort();
+    let iterations = options.iterations as u64;
+    Ok(Stats {
+        iterations: options.iterations,
+        min: times[0],
+        median: times[times.len() / 2],
+        max: times[times.len() - 1],
+        allocations: (allocations_after - allocations_before) / iterations,
+        bytes: (bytes_after - bytes_before) / iterations,
+    })
+}
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn measuring() {
+    let options = Options {
+        warmup: 1,
+        iterations: 5,
+    };
+    let stats = measure(options, || Ok(vec![0u8; 1000])).unwrap();
+    assert_eq!(stats.iterations, 5);
+    assert!(stats.min <= stats.median && stats.median <= stats.max);
+    // Other tests allocate at the same time, so these are only lower bounds.
+    assert!(stats.allocations >= 1);
+    assert!(stats.bytes >= 1000);
+
+    let mut runs = 0;
+    let failing = measure(options, || {
+        runs += 1;
+        Err::<(), _>(std::io::Error::other("failed").into())
+    });
+    assert!(failing.is_err());
+    assert_eq!(runs, 1);
+}
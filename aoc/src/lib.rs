@@ -0,0 +1,121 @@
+pub mod answers;
+pub mod bench;
+pub mod scaffold;
+pub mod server;
+pub mod watch;
+
+use aoc_core::{Answer, Rng};
+use std::path::PathBuf;
+
+/// Solves one part of a puzzle.
+pub type Solver = fn(&str) -> aoc_core::Result<Answer>;
+
+/// Parses a puzzle input, throwing away the result.
+pub type Parser = fn(&str) -> aoc_core::Result<()>;
+
+/// Generates a random, valid puzzle input of the given size.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+pub struct Day {
+    pub number: u8,
+    parse: Parser,
+    generate: Generator,
+    parts: [Solver; 2],
+}
+
+macro_rules! days {
+    ($($number:literal => $krate:ident),* $(,)?) => {
+        /// Every day that has been solved, in order.
+        pub const DAYS: &[Day] = &[
+            $(Day {
+                number: $number,
+                parse: |input| $krate::parse(input.as_bytes()).map(|p| drop(std::hint::black_box(p))),
+                generate: $krate::generate,
+                parts: [
+                    |input| $krate::solve_part1(input.as_bytes()).map(Answer::from),
+                    |input| $krate::solve_part2(input.as_bytes()).map(Answer::from),
+                ],
+            }),*
+        ];
+    };
+}
+
+days! {
+    1 => day1,
+    2 => day2,
+    3 => day3,
+    4 => day4,
+    5 => day5,
+    6 => day6,
+    7 => day7,
+    8 => day8,
+    9 => day9,
+    10 => day10,
+    11 => day11,
+    12 => day12,
+}
+
+impl Day {
+    pub fn get(number: u8) -> Option<&'static Day> {
+        DAYS.iter().find(|day| day.number == number)
+    }
+
+    /// Parses this day's puzzle input, without solving either part.
+    pub fn parse(&self, input: &str) -> aoc_core::Result<()> {
+        (self.parse)(input)
+    }
+
+    /// Solves the given part (1 or 2) of this day's puzzle.
+    pub fn solve(&self, part: u8, input: &str) -> Option<aoc_core::Result<Answer>> {
+        let solver = self.parts.get(usize::from(part).checked_sub(1)?)?;
+        Some(solver(input))
+    }
+
+    /// A random puzzle input, of a size (a number of lines, or of items, or a side of a grid)
+    /// that depends on the day.
+    pub fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        (self.generate)(rng, size)
+    }
+
+    /// The day's crate, where its inputs are checked in.
+    pub fn dir(&self) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(format!("day{}", self.number))
+    }
+
+    /// The puzzle input checked in alongside the day's crate.
+    pub fn default_input(&self) -> PathBuf {
+        self.dir().join("input")
+    }
+}
+
+#[test]
+fn days_in_order() {
+    for (i, day) in DAYS.iter().enumerate() {
+        assert_eq!(usize::from(day.number), i + 1);
+    }
+}
+
+#[test]
+fn generated_inputs() {
+    for day in DAYS {
+        for (seed, size) in [(0, 0), (1, 1), (2, 10), (3, 50)] {
+            let input = day.generate(&mut Rng::new(seed), size);
+            for part in [1, 2] {
+                let answer = day.solve(part, &input).unwrap();
+                assert!(
+                    answer.is_ok(),
+                    "day {}, part {}, seed {}: {}\n{}",
+                    day.number,
+                    part,
+                    seed,
+                    answer.unwrap_err(),
+                    input
+                );
+            }
+        }
+        let same_seed = day.generate(&mut Rng::new(7), 20);
+        assert_eq!(same_seed, day.generate(&mut Rng::new(7), 20));
+    }
+}
@@ -0,0 +1,554 @@
+use aoc::{
+    bench::{self, CountingAllocator, Options, Stats},
+    scaffold,
+    server::{self, Server},
+    watch, Day, DAYS,
+};
+use aoc_core::{
+    json::{Object, ToJson},
+    report, Format, InFile, Input, Rng,
+};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::{self, ExitCode},
+    thread,
+    time::{Duration, Instant},
+};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "\
+Usage: aoc run <day> [--part <1|2>] [--input <path|->] [--format <text|json>]
+       aoc verify [<day>]
+       aoc bench [<day>] [--iterations <n>] [--warmup <n>] [--input <path|->]
+                 [--format <text|json>]
+       aoc generate <day> [--size <n>] [--seed <n>]
+       aoc new <day>
+       aoc watch <day> [--interval <ms>]
+       aoc serve [--port <n>] [--threads <n>] [--max-body <bytes>]
+                 [--solve-timeout <seconds>]";
+
+enum Command {
+    Run(RunArgs),
+    /// Checks the known answers, of one day or of every day.
+    Verify(Option<u8>),
+    Bench(BenchArgs),
+    Generate(GenerateArgs),
+    /// Creates the crate for a new day.
+    New(u8),
+    /// Solves puzzles over HTTP, on localhost.
+    Serve(ServeArgs),
+    Watch(WatchArgs),
+}
+
+struct RunArgs {
+    day: u8,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    format: Format,
+}
+
+struct BenchArgs {
+    /// Every day, if none is given.
+    day: Option<u8>,
+    options: Options,
+    input: Option<PathBuf>,
+    format: Format,
+}
+
+struct WatchArgs {
+    day: u8,
+    /// How long to wait between looking for changes.
+    interval: Duration,
+}
+
+struct ServeArgs {
+    port: u16,
+    config: server::Config,
+}
+
+struct GenerateArgs {
+    day: u8,
+    size: usize,
+    seed: u64,
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    day.parse::<u8>()
+        .map_err(|_| format!("{} is not a day", day))
+}
+
+fn get_day(number: u8) -> Result<&'static Day, String> {
+    Day::get(number).ok_or(format!("Day {} has not been solved", number))
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("run") => parse_run_args(args).map(Command::Run),
+        Some("verify") => {
+            let day = args.next().map(|day| parse_day(&day)).transpose()?;
+            if let Some(unexpected) = args.next() {
+                return Err(format!("Unexpected argument {}", unexpected));
+            }
+            Ok(Command::Verify(day))
+        }
+        Some("bench") => parse_bench_args(args).map(Command::Bench),
+        Some("generate") => parse_generate_args(args).map(Command::Generate),
+        Some("new") => {
+            let day = parse_day(&args.next().ok_or("Expected a day to create")?)?;
+            if let Some(unexpected) = args.next() {
+                return Err(format!("Unexpected argument {}", unexpected));
+            }
+            Ok(Command::New(day))
+        }
+        Some("watch") => parse_watch_args(args).map(Command::Watch),
+        Some("serve") => parse_serve_args(args).map(Command::Serve),
+        Some(other) => Err(format!("Unknown command {}", other)),
+        None => Err("Expected a command".to_string()),
+    }
+}
+
+fn parse_run_args(mut args: impl Iterator<Item = String>) -> Result<RunArgs, String> {
+    let day = parse_day(&args.next().ok_or("Expected a day to run")?)?;
+
+    let mut run_args = RunArgs {
+        day,
+        part: None,
+        input: None,
+        format: Format::Text,
+    };
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or(format!("Expected a value after {}", flag))
+        };
+        match flag.as_str() {
+            "--part" => {
+                let part = value()?;
+                match part.as_str() {
+                    "1" => run_args.part = Some(1),
+                    "2" => run_args.part = Some(2),
+                    _ => return Err(format!("{} is not a part", part)),
+                }
+            }
+            "--input" => run_args.input = Some(value()?.into()),
+            "--format" => run_args.format = value()?.parse()?,
+            _ => return Err(format!("Unknown option {}", flag)),
+        }
+    }
+    Ok(run_args)
+}
+
+fn parse_bench_args(args: impl Iterator<Item = String>) -> Result<BenchArgs, String> {
+    let mut args = args.peekable();
+    let day = match args.peek() {
+        Some(day) if !day.starts_with("--") => Some(parse_day(day)?),
+        _ => None,
+    };
+    if day.is_some() {
+        args.next();
+    }
+
+    let mut bench_args = BenchArgs {
+        day,
+        options: Options::default(),
+        input: None,
+        format: Format::Text,
+    };
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or(format!("Expected a value after {}", flag))
+        };
+        let count = |value: String| match value.parse::<usize>() {
+            Ok(count) => Ok(count),
+            Err(_) => Err(format!("{} is not a number of runs", value)),
+        };
+        match flag.as_str() {
+            "--iterations" => match count(value()?)? {
+                0 => return Err("Expected at least one iteration".to_string()),
+                iterations => bench_args.options.iterations = iterations,
+            },
+            "--warmup" => bench_args.options.warmup = count(value()?)?,
+            "--input" => bench_args.input = Some(value()?.into()),
+            "--format" => bench_args.format = value()?.parse()?,
+            _ => return Err(format!("Unknown option {}", flag)),
+        }
+    }
+    if bench_args.input.is_some() && bench_args.day.is_none() {
+        return Err("Expected a day to go with --input".to_string());
+    }
+    Ok(bench_args)
+}
+
+fn parse_watch_args(mut args: impl Iterator<Item = String>) -> Result<WatchArgs, String> {
+    let day = parse_day(&args.next().ok_or("Expected a day to watch")?)?;
+
+    let mut watch_args = WatchArgs {
+        day,
+        interval: Duration::from_millis(500),
+    };
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or(format!("Expected a value after {}", flag))?;
+        match flag.as_str() {
+            "--interval" => match value.parse::<u64>() {
+                Ok(ms) if ms > 0 => watch_args.interval = Duration::from_millis(ms),
+                _ => return Err(format!("{} is not a number of milliseconds", value)),
+            },
+            _ => return Err(format!("Unknown option {}", flag)),
+        }
+    }
+    Ok(watch_args)
+}
+
+fn parse_serve_args(mut args: impl Iterator<Item = String>) -> Result<ServeArgs, String> {
+    let mut serve_args = ServeArgs {
+        port: 8022,
+        config: server::Config::default(),
+    };
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or(format!("Expected a value after {}", flag))?;
+        let number = |what| {
+            value
+                .parse::<usize>()
+                .map_err(|_| format!("{} is not a {}", value, what))
+        };
+        match flag.as_str() {
+            "--port" => {
+                serve_args.port = value
+                    .parse()
+                    .map_err(|_| format!("{} is not a port", value))?
+            }
+            "--threads" => match number("number of threads")? {
+                0 => return Err("Expected at least one thread".to_string()),
+                threads => serve_args.config.threads = threads,
+            },
+            "--max-body" => serve_args.config.max_body = number("size in bytes")?,
+            "--solve-timeout" => match number("number of seconds")? {
+                0 => return Err("Expected a timeout of at least a second".to_string()),
+                seconds => serve_args.config.solve_timeout = Duration::from_secs(seconds as u64),
+            },
+            _ => return Err(format!("Unknown option {}", flag)),
+        }
+    }
+    Ok(serve_args)
+}
+
+fn parse_generate_args(mut args: impl Iterator<Item = String>) -> Result<GenerateArgs, String> {
+    let day = parse_day(
+        &args
+            .next()
+            .ok_or("Expected a day to generate an input for")?,
+    )?;
+
+    let mut generate_args = GenerateArgs {
+        day,
+        size: 100,
+        seed: 0,
+    };
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or(format!("Expected a value after {}", flag))?;
+        let number = || match value.parse() {
+            Ok(number) => Ok(number),
+            Err(_) => Err(format!("{} is not a number", value)),
+        };
+        match flag.as_str() {
+            "--size" => generate_args.size = number()? as usize,
+            "--seed" => generate_args.seed = number()?,
+            _ => return Err(format!("Unknown option {}", flag)),
+        }
+    }
+    Ok(generate_args)
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let day = get_day(args.day)?;
+    let path = args.input.unwrap_or_else(|| day.default_input());
+    let input = Input::read(&path).map_err(|e| e.to_string())?;
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    for part in parts {
+        let start = Instant::now();
+        let answer = day
+            .solve(part, input.contents())
+            .expect("the part to be 1 or 2")
+            .in_file(input.name())
+            .map_err(|e| format!("Could not solve day {}: {}", day.number, e))?;
+        let elapsed = start.elapsed();
+        if args.format == Format::Json {
+            let json = report::answer_json(day.number, part, &answer, elapsed);
+            println!("{}", json);
+            continue;
+        }
+        let answer = answer.to_string();
+        if answer.contains('\n') {
+            println!("Day {}, part {}:\n{}", day.number, part, answer);
+        } else {
+            println!("Day {}, part {}: {}", day.number, part, answer);
+        }
+    }
+    Ok(())
+}
+
+/// Indents every line of a (possibly multiline) answer.
+fn indented(answer: &str) -> String {
+    answer
+        .lines()
+        .map(|line| format!("    {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn verify(day: Option<u8>) -> Result<(), String> {
+    let days = match day {
+        Some(number) => vec![get_day(number)?],
+        None => DAYS.iter().collect(),
+    };
+
+    let (mut checked, mut wrong) = (0, 0);
+    for day in days {
+        let outcomes = day
+            .verify()
+            .map_err(|e| format!("Could not verify day {}: {}", day.number, e))?;
+        for outcome in outcomes {
+            checked += 1;
+            let expected = &outcome.expected;
+            let label = format!(
+                "Day {}, part {}, {}",
+                day.number, expected.part, expected.file
+            );
+            if outcome.is_ok() {
+                println!("{}: ok", label);
+                continue;
+            }
+            wrong += 1;
+            match &outcome.got {
+                Ok(got) => println!(
+                    "{}: WRONG\n  expected:\n{}\n  got:\n{}",
+                    label,
+                    indented(&expected.answer),
+                    indented(got)
+                ),
+                Err(e) => println!("{}: FAILED\n{}", label, indented(&e.to_string())),
+            }
+        }
+    }
+
+    if wrong > 0 {
+        return Err(format!("{} of {} answers are wrong", wrong, checked));
+    }
+    println!("All {} answers are right", checked);
+    Ok(())
+}
+
+/// Formats a duration to four significant digits or so, padded to line up in a column.
+fn duration(duration: Duration) -> String {
+    format!("{:>10}", format!("{:.3?}", duration))
+}
+
+fn bench(args: BenchArgs) -> Result<(), String> {
+    let days = match args.day {
+        Some(number) => vec![get_day(number)?],
+        None => DAYS.iter().collect(),
+    };
+
+    let mut results = vec![];
+    for day in days {
+        let path = args.input.clone().unwrap_or_else(|| day.default_input());
+        let input = Input::read(&path).map_err(|e| e.to_string())?;
+        let contents = input.contents();
+        let stages: [(&str, &dyn Fn() -> aoc_core::Result<()>); 3] = [
+            ("parse", &|| day.parse(contents)),
+            ("part1", &|| day.solve(1, contents).unwrap().map(drop)),
+            ("part2", &|| day.solve(2, contents).unwrap().map(drop)),
+        ];
+        for (stage, run) in stages {
+            let stats = bench::measure(args.options, run)
+                .in_file(input.name())
+                .map_err(|e| format!("Could not benchmark day {}: {}", day.number, e))?;
+            if args.format == Format::Text {
+                println!(
+                    "Day {:<2} {}  min {}  median {}  max {}  {:>8} allocations  {:>10} bytes",
+                    day.number,
+                    stage,
+                    duration(stats.min),
+                    duration(stats.median),
+                    duration(stats.max),
+                    stats.allocations,
+                    stats.bytes
+                );
+            }
+            results.push(stats_json(day.number, stage, &stats));
+        }
+    }
+
+    if args.format == Format::Json {
+        println!("{}", results.to_json());
+    }
+    Ok(())
+}
+
+fn stats_json(day: u8, stage: &str, stats: &Stats) -> Object {
+    Object::new()
+        .field("day", day)
+        .field("stage", stage)
+        .field("iterations", stats.iterations)
+        .field("min_ns", stats.min.as_nanos())
+        .field("median_ns", stats.median.as_nanos())
+        .field("max_ns", stats.max.as_nanos())
+        .field("allocations", stats.allocations)
+        .field("bytes", stats.bytes)
+}
+
+fn generate(args: GenerateArgs) -> Result<(), String> {
+    let day = get_day(args.day)?;
+    print!("{}", day.generate(&mut Rng::new(args.seed), args.size));
+    Ok(())
+}
+
+fn new(day: u8) -> Result<(), String> {
+    let created = scaffold::scaffold(&scaffold::workspace(), day)
+        .map_err(|e| format!("Could not create day {}: {}", day, e))?;
+    for file in created {
+        println!("Created {}", file.display());
+    }
+    println!(
+        "Added day{0} to the workspace. Once it's solved, add it to the aoc runner's Cargo.toml \
+         and to the days! in aoc/src/lib.rs.",
+        day
+    );
+    Ok(())
+}
+
+fn watch(args: WatchArgs) -> Result<(), String> {
+    let day = get_day(args.day)?;
+    let list = |files: io::Result<Vec<PathBuf>>| {
+        files.map_err(|e| format!("Could not list day {}'s files: {}", day.number, e))
+    };
+    // The known answers are watched along with the inputs, as changing them changes the report.
+    let watched_inputs = || {
+        let mut inputs = list(day.inputs())?;
+        inputs.push(day.answers_file());
+        Ok::<_, String>(watch::stamps(&inputs))
+    };
+
+    // Found before any rebuild, which would leave this process's executable deleted.
+    let exe = std::env::current_exe().map_err(|e| format!("Could not find the runner: {}", e))?;
+    let mut sources = watch::stamps(&list(day.sources())?);
+    let mut inputs = watched_inputs()?;
+    let mut previous = vec![];
+    println!(
+        "Watching day {}'s inputs and sources (Ctrl-C to stop)",
+        day.number
+    );
+    loop {
+        match watch::solve(day, &list(day.inputs())?) {
+            Ok(solved) => {
+                println!("{}", watch::report(&previous, &solved));
+                previous = solved;
+            }
+            Err(e) => println!("Could not solve day {}: {}", day.number, e),
+        }
+
+        loop {
+            thread::sleep(args.interval);
+            let now = watch::stamps(&list(day.sources())?);
+            if !watch::changed(&sources, &now).is_empty() {
+                sources = now;
+                println!("\nThe sources changed; rebuilding");
+                if rebuild()? {
+                    return Err(restart(&exe));
+                }
+                println!("The build failed; still watching");
+            }
+            let now = watched_inputs()?;
+            let changed = watch::changed(&inputs, &now);
+            if !changed.is_empty() {
+                inputs = now;
+                let names: Vec<_> = changed
+                    .iter()
+                    .filter_map(|file| file.file_name())
+                    .map(|name| name.to_string_lossy())
+                    .collect();
+                println!("\n{} changed", names.join(", "));
+                break;
+            }
+        }
+    }
+}
+
+/// Builds the runner again (as it was built, debug or release), giving whether that worked.
+fn rebuild() -> Result<bool, String> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut build = process::Command::new(cargo);
+    build.args(["build", "-p", "aoc"]);
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    let status = build
+        .status()
+        .map_err(|e| format!("Could not run cargo: {}", e))?;
+    Ok(status.success())
+}
+
+/// Replaces this process with the new build of the runner, run with the same arguments. Only
+/// returns if that fails.
+fn restart(exe: &Path) -> String {
+    let mut command = process::Command::new(exe);
+    command.args(std::env::args_os().skip(1));
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        format!("Could not restart: {}", command.exec())
+    }
+    #[cfg(not(unix))]
+    match command.status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => format!("Could not restart: {}", e),
+    }
+}
+
+fn serve(args: ServeArgs) -> Result<(), String> {
+    let server = Server::bind(("127.0.0.1", args.port), args.config)
+        .map_err(|e| format!("Could not listen on port {}: {}", args.port, e))?;
+    let address = server.local_addr().map_err(|e| e.to_string())?;
+    println!("Listening on http://{}/day/{{n}}/part/{{p}}", address);
+    server.run()
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let result = match args {
+        Command::Run(args) => run(args),
+        Command::Verify(day) => verify(day),
+        Command::Bench(args) => bench(args),
+        Command::Generate(args) => generate(args),
+        Command::New(day) => new(day),
+        Command::Serve(args) => serve(args),
+        Command::Watch(args) => watch(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
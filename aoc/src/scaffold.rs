@@ -0,0 +1,209 @@
+//! Creating the crate for a new day, with the same structure as every other day.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The workspace, where the days' crates are.
+pub fn workspace() -> PathBuf {
+    let runner = Path::new(env!("CARGO_MANIFEST_DIR"));
+    runner.parent().unwrap_or(runner).to_path_buf()
+}
+
+/// Creates `dayN` in the `workspace`, and adds it to the workspace's members. Returns the files
+/// created.
+pub fn scaffold(workspace: &Path, day: u8) -> io::Result<Vec<PathBuf>> {
+    let name = format!("day{}", day);
+    let dir = workspace.join(&name);
+    if dir.exists() {
+        let message = format!("{} already exists", dir.display());
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, message));
+    }
+    let manifest_path = workspace.join("Cargo.toml");
+    let manifest = register(&fs::read_to_string(&manifest_path)?, &name)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let files = [
+        ("Cargo.toml", cargo_toml(&name)),
+        ("src/lib.rs", LIB.to_string()),
+        ("src/main.rs", main_rs(day)),
+        ("src/generate.rs", GENERATE.to_string()),
+        ("answers", ANSWERS.to_string()),
+        ("exampleinput", String::new()),
+        ("input", String::new()),
+    ];
+    fs::create_dir_all(dir.join("src"))?;
+    let mut created = vec![];
+    for (file, contents) in files {
+        let path = dir.join(file);
+        fs::write(&path, contents)?;
+        created.push(path);
+    }
+    fs::write(&manifest_path, manifest)?;
+    Ok(created)
+}
+
+/// Adds the crate to the workspace's `members`, after the days before it.
+fn register(manifest: &str, name: &str) -> Result<String, String> {
+    let number = |member: &str| -> Option<u32> {
+        member
+            .trim()
+            .trim_end_matches(',')
+            .trim_matches('"')
+            .strip_prefix("day")?
+            .parse()
+            .ok()
+    };
+    let day = number(name).ok_or_else(|| format!("{} is not a day's crate name", name))?;
+
+    let lines: Vec<&str> = manifest.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("members = ["))
+        .ok_or("expected a members list in the workspace's Cargo.toml")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim() == "]")
+            .ok_or("expected the members list to end with a `]` line")?;
+    let members = &lines[start + 1..end];
+    if members.iter().any(|member| number(member) == Some(day)) {
+        return Err(format!("{} is already a member of the workspace", name));
+    }
+    let insert_at = members
+        .iter()
+        .rposition(|member| number(member).is_some_and(|n| n < day))
+        .map_or(end, |i| start + 1 + i + 1);
+
+    let member = format!("    \"{}\",", name);
+    let mut registered: Vec<&str> = lines[..insert_at].to_vec();
+    registered.push(&member);
+    registered.extend(&lines[insert_at..]);
+    Ok(registered.join("\n") + "\n")
+}
+
+fn cargo_toml(name: &str) -> String {
+    format!(
+        "\
+[package]
+name = \"{}\"
+version = \"0.0.0\"
+edition = \"2021\"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc-core = {{ path = \"../aoc-core\" }}
+",
+        name
+    )
+}
+
+fn main_rs(day: u8) -> String {
+    format!(
+        "\
+use aoc_core::{{InFile, Input, Report}};
+
+fn main() -> aoc_core::Result<()> {{
+    let input = Input::from_args(concat!(env!(\"CARGO_MANIFEST_DIR\"), \"/input\"))?;
+    let report = Report::from_args({day})?;
+    report.part(1, \"Part 1 answer: {{}}\", || {{
+        day{day}::solve_part1(input.as_bytes()).in_file(input.name())
+    }})?;
+    report.part(2, \"Part 2 answer: {{}}\", || {{
+        day{day}::solve_part2(input.as_bytes()).in_file(input.name())
+    }})?;
+    Ok(())
+}}
+",
+        day = day
+    )
+}
+
+const LIB: &str = "\
+mod generate;
+
+pub use generate::generate;
+
+use aoc_core::Result;
+use std::io::{self, ErrorKind, Read};
+
+#[test]
+#[ignore = \"the example's answers are yet to be filled in\"]
+fn exampleinput() {
+    let input = std::fs::File::open(\"exampleinput\").unwrap();
+    assert_eq!(solve_part1(input).unwrap(), 0);
+    let input = std::fs::File::open(\"exampleinput\").unwrap();
+    assert_eq!(solve_part2(input).unwrap(), 0);
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub fn parse(input: impl Read) -> Result<impl Sized> {
+    Ok(io::read_to_string(input)?)
+}
+
+pub fn solve_part1(input: impl Read) -> Result<u64> {
+    let _input = parse(input)?;
+    Err(io::Error::new(ErrorKind::Unsupported, \"not solved yet\").into())
+}
+
+pub fn solve_part2(input: impl Read) -> Result<u64> {
+    let _input = parse(input)?;
+    Err(io::Error::new(ErrorKind::Unsupported, \"not solved yet\").into())
+}
+";
+
+const GENERATE: &str = "\
+use aoc_core::Rng;
+
+/// A random, valid puzzle input of about `size` lines.
+pub fn generate(_rng: &mut Rng, _size: usize) -> String {
+    String::new()
+}
+";
+
+const ANSWERS: &str = "\
+# file       part  answer
+# exampleinput 1     ...
+# exampleinput 2     ...
+# input        1     ...
+# input        2     ...
+";
+
+#[test]
+fn registering() {
+    let manifest =
+        "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day2\",\n    \"day10\",\n]\n";
+    let registered = register(manifest, "day3").unwrap();
+    assert_eq!(
+        registered,
+        "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day2\",\n    \"day3\",\n    \"day10\",\n]\n"
+    );
+    let registered = register(manifest, "day11").unwrap();
+    assert!(registered.contains("\"day10\",\n    \"day11\",\n]"));
+    assert!(register(manifest, "day2").is_err());
+    assert!(register("[workspace]\n", "day3").is_err());
+    assert!(register(manifest, "aoc").is_err());
+}
+
+#[test]
+fn scaffolding() {
+    let workspace = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+    fs::create_dir_all(&workspace).unwrap();
+    fs::write(
+        workspace.join("Cargo.toml"),
+        "members = [\n    \"day1\",\n]\n",
+    )
+    .unwrap();
+
+    let created = scaffold(&workspace, 2).unwrap();
+    assert!(created.iter().all(|file| file.exists()));
+    let main = fs::read_to_string(workspace.join("day2/src/main.rs")).unwrap();
+    assert!(main.contains("Report::from_args(2)") && main.contains("day2::solve_part1"));
+    let manifest = fs::read_to_string(workspace.join("Cargo.toml")).unwrap();
+    assert_eq!(manifest, "members = [\n    \"day1\",\n    \"day2\",\n]\n");
+    assert!(scaffold(&workspace, 2).is_err());
+
+    fs::remove_dir_all(&workspace).unwrap();
+}
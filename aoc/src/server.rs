@@ -0,0 +1,459 @@
+//! A small HTTP/1.1 server for solving puzzles: `POST /day/{n}/part/{p}` with the input as the
+//! body answers with the same JSON as `--format json`.
+//!
+//! Connections are handed to a fixed number of worker threads through a bounded queue; when the
+//! queue is full, the server answers `503` straight away rather than letting the backlog grow.
+//! Each connection serves a single request.
+//!
+//! A solver that runs past its deadline gets a `503` too. It can't be stopped partway, though, so
+//! its worker stays busy until it finishes; a run of such requests ties up every worker, and the
+//! rest are turned away as too busy until they're done.
+
+use crate::Day;
+use aoc_core::{json::Object, report};
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, TrySendError},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How long to wait after failing to accept a connection, so that a lasting failure doesn't spin.
+const ACCEPT_PAUSE: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Worker threads, each serving one connection at a time.
+    pub threads: usize,
+    /// Connections that may wait for a worker.
+    pub queue: usize,
+    /// The largest request line and headers accepted, in bytes.
+    pub max_head: usize,
+    /// The largest body (puzzle input) accepted, in bytes.
+    pub max_body: usize,
+    /// How long to wait on a slow client, reading or writing.
+    pub timeout: Duration,
+    /// How long a solver has to answer.
+    pub solve_timeout: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            threads: 4,
+            queue: 16,
+            max_head: 8 * 1024,
+            max_body: 1024 * 1024,
+            timeout: Duration::from_secs(10),
+            solve_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    /// Always JSON.
+    pub body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: Object) -> Self {
+        Response {
+            status,
+            headers: vec![],
+            body: body.to_string(),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Response::json(status, Object::new().field("error", message.into()))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            411 => "Length Required",
+            413 => "Content Too Large",
+            422 => "Unprocessable Content",
+            431 => "Request Header Fields Too Large",
+            500 => "Internal Server Error",
+            503 => "Service Unavailable",
+            _ => "",
+        }
+    }
+
+    pub fn write_to(&self, mut out: impl Write) -> io::Result<()> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, self.reason());
+        head.push_str("Content-Type: application/json\r\n");
+        head.push_str(&format!("Content-Length: {}\r\n", self.body.len()));
+        head.push_str("Connection: close\r\n");
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str("\r\n");
+        out.write_all(head.as_bytes())?;
+        out.write_all(self.body.as_bytes())?;
+        out.flush()
+    }
+}
+
+/// Reads a request, or gives the response refusing it.
+pub fn read_request(mut input: impl BufRead, config: &Config) -> Result<Request, Response> {
+    let io_error =
+        |e: io::Error| Response::error(400, format!("could not read the request: {}", e));
+
+    // The request line and headers, with a limit on their size as a whole.
+    let mut head = vec![];
+    let mut remaining = config.max_head;
+    loop {
+        let mut line = String::new();
+        let read = (&mut input)
+            .take(remaining as u64)
+            .read_line(&mut line)
+            .map_err(io_error)?;
+        if !line.ends_with('\n') {
+            if read == remaining {
+                return Err(Response::error(431, "the request's headers are too large"));
+            }
+            return Err(Response::error(400, "the request ended early"));
+        }
+        remaining -= read;
+        let line = line.trim_end_matches(['\r', '\n']).to_string();
+        if line.is_empty() {
+            break;
+        }
+        head.push(line);
+    }
+
+    let request_line = head
+        .first()
+        .ok_or_else(|| Response::error(400, "expected a request line"))?;
+    let (method, path, version) = match request_line.split(' ').collect::<Vec<_>>()[..] {
+        [method, path, version] => (method, path, version),
+        _ => return Err(Response::error(400, "malformed request line")),
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(Response::error(
+            400,
+            format!("unsupported version {}", version),
+        ));
+    }
+
+    let mut content_length = None;
+    for header in &head[1..] {
+        let (name, value) = header
+            .split_once(':')
+            .ok_or_else(|| Response::error(400, "malformed header"))?;
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            let length = value
+                .parse::<usize>()
+                .map_err(|_| Response::error(400, "malformed Content-Length"))?;
+            content_length = Some(length);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(Response::error(
+                411,
+                "chunked bodies aren't supported; send a Content-Length",
+            ));
+        }
+    }
+
+    let mut body = vec![];
+    match content_length {
+        Some(length) if length > config.max_body => {
+            let message = format!("the body is larger than {} bytes", config.max_body);
+            return Err(Response::error(413, message));
+        }
+        Some(length) => {
+            body.resize(length, 0);
+            input.read_exact(&mut body).map_err(io_error)?;
+        }
+        None if method == "POST" => {
+            return Err(Response::error(411, "expected a Content-Length"));
+        }
+        None => {}
+    }
+
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        body,
+    })
+}
+
+/// Routes the request to the solver it asks for.
+pub fn handle(request: &Request) -> Response {
+    let path = request.path.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let (day, part) = match segments[..] {
+        ["day", day, "part", part] => (day, part),
+        _ => return Response::error(404, "no such route; expected /day/{n}/part/{p}"),
+    };
+    if request.method != "POST" {
+        let mut response = Response::error(405, "expected a POST, with the input as its body");
+        response.headers.push(("Allow", "POST".to_string()));
+        return response;
+    }
+    let day = match day.parse().ok().and_then(Day::get) {
+        Some(day) => day,
+        None => return Response::error(404, format!("day {} has not been solved", day)),
+    };
+    let part = match part {
+        "1" => 1,
+        "2" => 2,
+        _ => return Response::error(404, format!("{} is not a part", part)),
+    };
+    let input = match std::str::from_utf8(&request.body) {
+        Ok(input) => input,
+        Err(_) => return Response::error(400, "the input isn't UTF-8"),
+    };
+
+    let start = Instant::now();
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| day.solve(part, input).unwrap()));
+    let elapsed = start.elapsed();
+    match solved {
+        Ok(Ok(answer)) => {
+            Response::json(200, report::answer_json(day.number, part, &answer, elapsed))
+        }
+        Ok(Err(e)) => {
+            let mut error = Object::new().field("error", e.to_string());
+            if let Some(e) = e.as_parse() {
+                error = error
+                    .field("message", e.message())
+                    .field("line", e.line())
+                    .field("column", e.column());
+            }
+            Response::json(422, error)
+        }
+        Err(_) => Response::error(500, "the solver panicked"),
+    }
+}
+
+fn serve(stream: TcpStream, config: &Config) {
+    // A client that's gone quiet, or gone away, only costs its own response.
+    let _ = stream.set_read_timeout(Some(config.timeout));
+    let _ = stream.set_write_timeout(Some(config.timeout));
+    let request = match read_request(BufReader::new(&stream), config) {
+        Ok(request) => request,
+        Err(response) => {
+            let _ = response.write_to(&stream);
+            return;
+        }
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let solver = thread::spawn(move || {
+        let _ = sender.send(handle(&request));
+    });
+    match receiver.recv_timeout(config.solve_timeout) {
+        Ok(response) => {
+            let _ = response.write_to(&stream);
+        }
+        Err(_) => {
+            let message = format!(
+                "the solver took longer than {} s",
+                config.solve_timeout.as_secs_f64()
+            );
+            let _ = Response::error(503, message).write_to(&stream);
+            drop(stream);
+            // There's no stopping the solver, so wait it out here rather than taking on more.
+            let _ = solver.join();
+        }
+    }
+}
+
+pub struct Server {
+    listener: TcpListener,
+    config: Config,
+}
+
+impl Server {
+    pub fn bind(address: impl ToSocketAddrs, config: Config) -> io::Result<Self> {
+        assert!(config.threads > 0, "expected at least one worker thread");
+        Ok(Server {
+            listener: TcpListener::bind(address)?,
+            config,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves connections for good. Failing to accept one (say, when out of file descriptors) is
+    /// logged, and the server carries on after a pause.
+    pub fn run(self) -> ! {
+        let config = Arc::new(self.config);
+        let (sender, receiver) = mpsc::sync_channel::<TcpStream>(config.queue);
+        let receiver = Arc::new(Mutex::new(receiver));
+        for _ in 0..config.threads {
+            let receiver = Arc::clone(&receiver);
+            let config = Arc::clone(&config);
+            thread::spawn(move || loop {
+                // The lock is only held while waiting, so it can't be poisoned.
+                let stream = receiver.lock().unwrap().recv();
+                match stream {
+                    Ok(stream) => serve(stream, &config),
+                    Err(_) => break,
+                }
+            });
+        }
+
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    eprintln!("Could not accept a connection: {}", e);
+                    thread::sleep(ACCEPT_PAUSE);
+                    continue;
+                }
+            };
+            match sender.try_send(stream) {
+                Ok(()) => {}
+                Err(TrySendError::Full(stream)) => {
+                    let _ = stream.set_write_timeout(Some(config.timeout));
+                    let _ = Response::error(503, "too busy; try again later").write_to(&stream);
+                }
+                Err(TrySendError::Disconnected(_)) => unreachable!("the workers never stop"),
+            }
+        }
+        unreachable!("incoming connections never run out")
+    }
+}
+
+#[cfg(test)]
+fn request(raw: &str) -> Result<Request, Response> {
+    let config = Config {
+        max_head: 64,
+        max_body: 16,
+        ..Config::default()
+    };
+    read_request(raw.as_bytes(), &config)
+}
+
+#[test]
+fn reading_requests() {
+    let parsed = request("POST /day/1/part/2 HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello").unwrap();
+    assert_eq!(
+        (parsed.method.as_str(), parsed.path.as_str()),
+        ("POST", "/day/1/part/2")
+    );
+    assert_eq!(parsed.body, b"hello");
+
+    let status = |raw| request(raw).unwrap_err().status;
+    assert_eq!(status("POST /day/1/part/2 HTTP/1.1\r\n\r\n"), 411);
+    assert_eq!(status("POST / HTTP/1.1\r\nContent-Length: 17\r\n\r\n"), 413);
+    assert_eq!(
+        status("POST / HTTP/1.1\r\nX-Padding: 0123456789012345678901234567890123456789\r\n\r\n"),
+        431
+    );
+    assert_eq!(
+        status("POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\nhi"),
+        400
+    );
+    assert_eq!(status("nonsense\r\n\r\n"), 400);
+}
+
+#[test]
+fn routing() {
+    let post = |path: &str, body: &str| Request {
+        method: "POST".to_string(),
+        path: path.to_string(),
+        body: body.as_bytes().to_vec(),
+    };
+    let response = handle(&post("/day/1/part/1", "1000\n2000\n\n4000\n"));
+    assert_eq!(response.status, 200);
+    assert!(response
+        .body
+        .starts_with(r#"{"day":1,"part":1,"answer":4000,"elapsed":"#));
+
+    let response = handle(&post("/day/2/part/1", "A Y\nB Q\n"));
+    assert_eq!(response.status, 422);
+    assert!(
+        response.body.contains(r#""line":2,"column":3"#),
+        "{}",
+        response.body
+    );
+
+    assert_eq!(handle(&post("/day/99/part/1", "")).status, 404);
+    assert_eq!(handle(&post("/day/1/part/3", "")).status, 404);
+    assert_eq!(handle(&post("/days", "")).status, 404);
+    let get = Request {
+        method: "GET".to_string(),
+        ..post("/day/1/part/1", "")
+    };
+    assert_eq!(handle(&get).status, 405);
+}
+
+#[test]
+fn deadline() {
+    let config = Config {
+        solve_timeout: Duration::from_nanos(1),
+        ..Config::default()
+    };
+    let server = Server::bind("127.0.0.1:0", config).unwrap();
+    let address = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    let input = std::fs::read_to_string("../day11/exampleinput").unwrap();
+    let request = format!(
+        "POST /day/11/part/2 HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+        input.len(),
+        input
+    );
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(
+        response.starts_with("HTTP/1.1 503 Service Unavailable\r\n"),
+        "{}",
+        response
+    );
+    assert!(
+        response.contains("the solver took longer than"),
+        "{}",
+        response
+    );
+}
+
+#[test]
+fn serving() {
+    let server = Server::bind("127.0.0.1:0", Config::default()).unwrap();
+    let address = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    let input = "A Y\nB X\nC Z\n";
+    let request = format!(
+        "POST /day/2/part/2 HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+        input.len(),
+        input
+    );
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+    assert!(
+        response.contains(r#"{"day":2,"part":2,"answer":12,"#),
+        "{}",
+        response
+    );
+}
@@ -0,0 +1,269 @@
+//! Watching a day's inputs and sources for changes, by polling their modification times, and
+//! comparing the answers from one run to the next.
+
+use crate::Day;
+use aoc_core::Input;
+use std::{
+    collections::BTreeMap,
+    fs, io, panic,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// When each file was last modified, and how large it was then.
+pub type Stamps = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+pub fn stamps(files: &[PathBuf]) -> Stamps {
+    // A file that can't be read (e.g. removed mid-save) is left out, and so counts as changed.
+    files
+        .iter()
+        .filter_map(|file| {
+            let metadata = fs::metadata(file).ok()?;
+            Some((file.clone(), (metadata.modified().ok()?, metadata.len())))
+        })
+        .collect()
+}
+
+/// The files that were added, removed or modified between the two stamps.
+pub fn changed(before: &Stamps, after: &Stamps) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(file, stamp)| before.get(*file) != Some(stamp))
+        .map(|(file, _)| file.clone())
+        .collect();
+    changed.extend(
+        before
+            .keys()
+            .filter(|file| !after.contains_key(*file))
+            .cloned(),
+    );
+    changed.sort();
+    changed
+}
+
+impl Day {
+    /// The day's puzzle inputs: `input`, `exampleinput`, `exampleinput2`, ...
+    pub fn inputs(&self) -> io::Result<Vec<PathBuf>> {
+        let mut inputs = vec![];
+        for entry in fs::read_dir(self.dir())? {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|name| name.to_str());
+            if matches!(name, Some(name) if name == "input" || name.starts_with("exampleinput"))
+                && path.is_file()
+            {
+                inputs.push(path);
+            }
+        }
+        inputs.sort();
+        Ok(inputs)
+    }
+
+    /// The day's manifest and Rust sources.
+    pub fn sources(&self) -> io::Result<Vec<PathBuf>> {
+        let mut sources = vec![self.dir().join("Cargo.toml")];
+        rust_files(&self.dir().join("src"), &mut sources)?;
+        sources.sort();
+        Ok(sources)
+    }
+}
+
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            rust_files(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// One part solved over one input, with the known answer if there is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solved {
+    pub file: String,
+    pub part: u8,
+    pub got: Result<String, String>,
+    pub expected: Option<String>,
+}
+
+impl Solved {
+    fn is_wrong(&self) -> bool {
+        match (&self.got, &self.expected) {
+            (Ok(got), Some(expected)) => got != expected,
+            (Err(_), _) => true,
+            (Ok(_), None) => false,
+        }
+    }
+}
+
+/// Solves both parts over each of the inputs.
+pub fn solve(day: &Day, inputs: &[PathBuf]) -> aoc_core::Result<Vec<Solved>> {
+    let answers = day.answers()?;
+    let mut solved = vec![];
+    for path in inputs {
+        let file = match path.strip_prefix(day.dir()) {
+            Ok(file) => file.display().to_string(),
+            Err(_) => path.display().to_string(),
+        };
+        let input = Input::read(path);
+        for part in [1, 2] {
+            let got = match &input {
+                // A panic is reported like any other failure, rather than ending the watch.
+                Ok(input) => match panic::catch_unwind(|| day.solve(part, input.contents())) {
+                    Ok(got) => got
+                        .expect("the part to be 1 or 2")
+                        .map(|answer| answer.to_string())
+                        .map_err(|e| e.in_file(input.name()).to_string()),
+                    Err(_) => Err("the solver panicked".to_string()),
+                },
+                Err(e) => Err(e.to_string()),
+            };
+            let expected = answers
+                .iter()
+                .find(|expected| expected.file == file && expected.part == part)
+                .map(|expected| expected.answer.clone());
+            solved.push(Solved {
+                file: file.clone(),
+                part,
+                got,
+                expected,
+            });
+        }
+    }
+    Ok(solved)
+}
+
+/// Describes how the answers changed since the `previous` run: the new and changed ones in full,
+/// and the unchanged ones only if they are wrong.
+pub fn report(previous: &[Solved], current: &[Solved]) -> String {
+    let mut lines = vec![];
+    let mut unchanged = 0;
+    for solved in current {
+        let label = format!("{}, part {}", solved.file, solved.part);
+        let before = previous
+            .iter()
+            .find(|before| before.file == solved.file && before.part == solved.part);
+        let verdict = match (&solved.got, &solved.expected) {
+            (Err(_), _) => " FAILED",
+            (Ok(_), Some(_)) if solved.is_wrong() => " WRONG",
+            (Ok(_), Some(_)) => " ok",
+            (Ok(_), None) => "",
+        };
+        match before {
+            Some(before) if before.got == solved.got => {
+                unchanged += 1;
+                if solved.is_wrong() {
+                    lines.push(format!("{}: still{}", label, verdict));
+                }
+                continue;
+            }
+            Some(before) => {
+                lines.push(format!("{}:{}", label, verdict));
+                lines.push(format!("  - {}", shown(&before.got)));
+                lines.push(format!("  + {}", shown(&solved.got)));
+            }
+            None => lines.push(format!("{}:{}\n    {}", label, verdict, shown(&solved.got))),
+        }
+        if let (Ok(_), Some(expected)) = (&solved.got, &solved.expected) {
+            if solved.is_wrong() {
+                lines.push(format!("  expected {}", expected.replace('\n', "\n    ")));
+            }
+        }
+    }
+    if unchanged > 0 {
+        lines.push(format!("({} unchanged)", unchanged));
+    }
+    lines.join("\n")
+}
+
+/// An answer or error, with its later lines indented to line up under its first.
+fn shown(got: &Result<String, String>) -> String {
+    let shown = match got {
+        Ok(answer) => answer,
+        Err(e) => e,
+    };
+    shown.replace('\n', "\n    ")
+}
+
+#[test]
+fn changes() {
+    let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let (a, b) = (dir.join("a"), dir.join("b"));
+    fs::write(&a, "1").unwrap();
+    let files = [a.clone(), b.clone()];
+    let before = stamps(&files);
+    assert!(changed(&before, &stamps(&files)).is_empty());
+
+    fs::write(&a, "22").unwrap();
+    fs::write(&b, "3").unwrap();
+    assert_eq!(changed(&before, &stamps(&files)), files);
+    let before = stamps(&files);
+    fs::remove_file(&a).unwrap();
+    assert_eq!(changed(&before, &stamps(&files)), [a]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn reporting() {
+    let solved = |file: &str, part, got: Result<&str, &str>, expected: Option<&str>| Solved {
+        file: file.to_string(),
+        part,
+        got: got.map(str::to_string).map_err(str::to_string),
+        expected: expected.map(str::to_string),
+    };
+    let first = [
+        solved("exampleinput", 1, Ok("24000"), Some("24000")),
+        solved("input", 1, Ok("100"), None),
+    ];
+    assert_eq!(
+        report(&[], &first),
+        "exampleinput, part 1: ok\n    24000\ninput, part 1:\n    100"
+    );
+
+    let second = [
+        solved("exampleinput", 1, Ok("23000"), Some("24000")),
+        solved("input", 1, Ok("100"), None),
+    ];
+    assert_eq!(
+        report(&first, &second),
+        "exampleinput, part 1: WRONG\n  - 24000\n  + 23000\n  expected 24000\n(1 unchanged)"
+    );
+    assert_eq!(
+        report(&second, &second),
+        "exampleinput, part 1: still WRONG\n(2 unchanged)"
+    );
+
+    let failed = [solved("input", 1, Err("expected a number"), None)];
+    assert!(report(&first[1..], &failed).ends_with("  - 100\n  + expected a number"));
+}
+
+#[test]
+fn watched_files() {
+    let day = Day::get(12).unwrap();
+    let inputs = day.inputs().unwrap();
+    let names: Vec<_> = inputs
+        .iter()
+        .map(|file| file.file_name().unwrap())
+        .collect();
+    assert_eq!(names, ["exampleinput", "exampleinput2", "input"]);
+    assert!(day
+        .sources()
+        .unwrap()
+        .contains(&day.dir().join("src/lib.rs")));
+
+    let solved = solve(day, &inputs).unwrap();
+    assert_eq!(solved.len(), 6);
+    // The second example has no way to the goal, which both parts report.
+    for (solved, from) in solved[2..4].iter().zip(["S", "any a"]) {
+        let message = format!("no path from {} to E\n", from);
+        assert!(solved.got.as_ref().is_err_and(|e| e.starts_with(&message)));
+    }
+    assert!(solved[..2]
+        .iter()
+        .chain(&solved[4..])
+        .all(|solved| !solved.is_wrong()));
+}
@@ -0,0 +1,242 @@
+use crate::{sum_elves, top_three_overflow, Elf, Ranked, Top};
+use aoc_core::{Error, ParseError, Result};
+use std::{
+    io::{self, Read},
+    num::NonZeroUsize,
+    sync::{mpsc, Mutex},
+    thread,
+};
+
+/// Solving over very large inputs: the input is read in large chunks, cut at blank lines so that
+/// no elf is split between two, and the chunks are summed on several threads at once, each
+/// keeping its own top `k`, before those are merged. The answers are the same as the serial
+/// solvers'.
+#[derive(Debug, Clone)]
+pub struct Chunked {
+    pub threads: usize,
+    /// How much is read at a time; a chunk is larger if an elf's block runs past it.
+    pub chunk_size: usize,
+}
+
+impl Default for Chunked {
+    /// A thread per core, and chunks of 4 MiB.
+    fn default() -> Self {
+        Chunked {
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            chunk_size: 4 << 20,
+        }
+    }
+}
+
+/// What came of summing a chunk, with its elves and lines counted from its start.
+struct Summed {
+    top: Top<u64>,
+    elves: usize,
+    lines: usize,
+}
+
+impl Chunked {
+    /// The `k` elves carrying the most calories, most first, as `crate::leaderboard` gives them.
+    pub fn leaderboard(&self, input: impl Read, k: usize) -> Result<Top<u64>> {
+        assert!(self.threads > 0, "expected at least one thread");
+        let (chunk_sender, chunks) = mpsc::sync_channel::<(usize, Vec<u8>)>(self.threads * 2);
+        let (summed_sender, summed) = mpsc::channel();
+        let chunks = Mutex::new(chunks);
+
+        let read = thread::scope(|scope| {
+            for _ in 0..self.threads {
+                let summed_sender = summed_sender.clone();
+                let chunks = &chunks;
+                scope.spawn(move || loop {
+                    let chunk = chunks.lock().unwrap().recv();
+                    let Ok((sequence, chunk)) = chunk else {
+                        break;
+                    };
+                    let _ = summed_sender.send((sequence, sum_chunk(&chunk, k)));
+                });
+            }
+            // The workers stop once the chunks run out and this is dropped.
+            let chunk_sender = chunk_sender;
+            self.read_chunks(input, |sequence, chunk| {
+                // Sending only fails if every worker is gone, which they aren't until this ends.
+                let _ = chunk_sender.send((sequence, chunk));
+            })
+        });
+        read?;
+        drop(summed_sender);
+
+        let mut summed: Vec<_> = summed.into_iter().collect();
+        summed.sort_by_key(|&(sequence, _)| sequence);
+        let mut top = Top::new(k);
+        let (mut elves, mut lines) = (0, 0);
+        for (_, chunk) in summed {
+            // As the chunks come in order, the first error is the one the serial solver gives.
+            let chunk = chunk.map_err(|e| moved_down(e, lines))?;
+            for ranked in chunk.top.iter() {
+                let Ranked { value, elf } = ranked;
+                let elf = Elf {
+                    index: elf.index + elves,
+                    lines: elf.lines.start() + lines..=elf.lines.end() + lines,
+                };
+                // Of equal totals, the one from the earlier chunk was inserted first.
+                top.insert(*value, elf);
+            }
+            elves += chunk.elves;
+            lines += chunk.lines;
+        }
+        Ok(top)
+    }
+
+    /// The most calories carried by a single elf, as `crate::solve_part1` gives it.
+    pub fn solve_part1(&self, input: impl Read) -> Result<u64> {
+        Ok(Self::most(&self.leaderboard(input, 1)?))
+    }
+
+    /// The calories carried by the top three elves, as `crate::solve_part2` gives them.
+    pub fn solve_part2(&self, input: impl Read) -> Result<u64> {
+        Self::top_three_total(&self.leaderboard(input, 3)?)
+    }
+
+    /// Part 1's answer from a leaderboard, so that one pass over input that can only be read once
+    /// can answer both parts.
+    pub fn most(top: &Top<u64>) -> u64 {
+        top.most().map_or(0, |ranked| ranked.value)
+    }
+
+    /// Part 2's answer from a leaderboard of at least three.
+    pub fn top_three_total(top: &Top<u64>) -> Result<u64> {
+        let total = top
+            .iter()
+            .take(3)
+            .try_fold(0u64, |total, ranked| total.checked_add(ranked.value));
+        total.ok_or_else(top_three_overflow)
+    }
+
+    /// Reads the input, handing it over in chunks (numbered in order) that end after a blank line
+    /// (except for the last).
+    fn read_chunks(
+        &self,
+        mut input: impl Read,
+        mut chunk: impl FnMut(usize, Vec<u8>),
+    ) -> Result<()> {
+        let mut sequence = 0;
+        let mut buffer = vec![];
+        loop {
+            let read = (&mut input)
+                .take(self.chunk_size.max(1) as u64)
+                .read_to_end(&mut buffer)?;
+            if read == 0 {
+                if !buffer.is_empty() {
+                    chunk(sequence, buffer);
+                }
+                return Ok(());
+            }
+            // Without a blank line yet, the elf's block carries on into the next read.
+            if let Some(end) = after_last_blank_line(&buffer) {
+                let rest = buffer.split_off(end);
+                chunk(sequence, buffer);
+                sequence += 1;
+                buffer = rest;
+            }
+        }
+    }
+}
+
+/// Where the last line made of nothing but (ASCII) whitespace ends, counting its `\n`.
+fn after_last_blank_line(buffer: &[u8]) -> Option<usize> {
+    let mut end = buffer.iter().rposition(|&b| b == b'\n')?;
+    loop {
+        let start = buffer[..end].iter().rposition(|&b| b == b'\n');
+        let line = &buffer[start.map_or(0, |start| start + 1)..end];
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return Some(end + 1);
+        }
+        end = start?;
+    }
+}
+
+fn sum_chunk(chunk: &[u8], k: usize) -> Result<Summed> {
+    let chunk = std::str::from_utf8(chunk).map_err(|_| {
+        // As `BufRead::lines` reports it.
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "stream did not contain valid UTF-8",
+        )
+    })?;
+    let mut top = Top::new(k);
+    let mut elves = 0;
+    sum_elves(chunk.lines().map(Ok), |calories, elf| {
+        elves += 1;
+        top.insert(calories, elf);
+    })?;
+    let lines = chunk.bytes().filter(|&b| b == b'\n').count();
+    Ok(Summed { top, elves, lines })
+}
+
+/// The error, with its line counted from the start of the input rather than of its chunk.
+fn moved_down(error: Error, lines: usize) -> Error {
+    match error.as_parse() {
+        Some(e) => ParseError::new(e.message(), e.line() + lines, e.column(), e.excerpt()).into(),
+        None => error,
+    }
+}
+
+#[test]
+fn matches_serial() {
+    use aoc_check::{ints, strings, Check};
+    use aoc_core::Rng;
+
+    let results = |top: Result<Top<u64>>| -> std::result::Result<Vec<Ranked<u64>>, String> {
+        top.map(|top| top.iter().cloned().collect())
+            .map_err(|e| e.to_string())
+    };
+    let chunked = |chunk_size, threads| Chunked {
+        threads: threads as usize,
+        chunk_size: chunk_size as usize,
+    };
+
+    // Valid inputs, cut into chunks of all sizes.
+    let cases = (ints(0, u64::MAX), (ints(1, 64), ints(1, 4)));
+    Check::new().cases(50).differential(
+        &cases,
+        |&(seed, (chunk_size, threads))| {
+            let input = crate::generate(&mut Rng::new(seed), 20);
+            results(chunked(chunk_size, threads).leaderboard(input.as_bytes(), 5))
+        },
+        |&(seed, _)| {
+            let input = crate::generate(&mut Rng::new(seed), 20);
+            results(crate::leaderboard(input.as_bytes(), 5))
+        },
+    );
+
+    // Anything at all, so that blank lines, stray spaces and errors turn up anywhere.
+    let cases = (strings("12 \n\n\r", 0), (ints(1, 8), ints(1, 3)));
+    Check::new().differential(
+        &cases,
+        |(input, (chunk_size, threads))| {
+            results(chunked(*chunk_size, *threads).leaderboard(input.as_bytes(), 3))
+        },
+        |(input, _)| results(crate::leaderboard(input.as_bytes(), 3)),
+    );
+}
+
+#[test]
+fn answers() {
+    let input = std::fs::read("input").unwrap();
+    let chunked = Chunked {
+        threads: 3,
+        chunk_size: 1000,
+    };
+    assert_eq!(
+        chunked.solve_part1(&input[..]).unwrap(),
+        crate::solve_part1(&input[..]).unwrap()
+    );
+    assert_eq!(
+        chunked.solve_part2(&input[..]).unwrap(),
+        crate::solve_part2(&input[..]).unwrap()
+    );
+
+    let input = format!("{}\n\n{}\n\n{}\n", u64::MAX, u64::MAX, u64::MAX);
+    let error = chunked.solve_part2(input.as_bytes()).unwrap_err();
+    assert!(matches!(error, Error::Overflow(_)), "{}", error);
+}
@@ -0,0 +1,15 @@
+use aoc_core::Rng;
+
+/// A random list of the calories carried by `elves` elves.
+pub fn generate(rng: &mut Rng, elves: usize) -> String {
+    let mut input = String::new();
+    for elf in 0..elves.max(1) {
+        if elf > 0 {
+            input.push('\n');
+        }
+        for _ in 0..rng.between(1, 15) {
+            input.push_str(&format!("{}\n", rng.between(1000, 60_000)));
+        }
+    }
+    input
+}
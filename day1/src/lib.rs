@@ -0,0 +1,271 @@
+mod chunked;
+mod generate;
+mod stats;
+mod top;
+
+pub use chunked::Chunked;
+pub use generate::generate;
+pub use stats::Statistics;
+pub use top::{Elf, Ranked, Top, TopK};
+
+use aoc_core::{Error, ParseError, Result};
+#[cfg(all(feature = "asm", target_arch = "x86_64"))]
+use std::arch::asm;
+use std::io::{self, BufRead, BufReader, Read};
+
+struct TopThree {
+    values: [u64; 3],
+}
+
+impl TopThree {
+    fn new() -> Self {
+        TopThree { values: [0; 3] }
+    }
+
+    fn try_insert(&mut self, value: u64) {
+        #[cfg(all(feature = "asm", target_arch = "x86_64"))]
+        self.try_insert_asm(value);
+        #[cfg(not(all(feature = "asm", target_arch = "x86_64")))]
+        self.try_insert_portable(value);
+    }
+
+    /// `try_insert` in plain Rust, for every target (and for Miri).
+    #[cfg_attr(all(feature = "asm", target_arch = "x86_64"), allow(dead_code))]
+    fn try_insert_portable(&mut self, value: u64) {
+        let [first, second, third] = &mut self.values;
+        if value >= *first {
+            (*first, *second, *third) = (value, *first, *second);
+        } else if value >= *second {
+            (*second, *third) = (value, *second);
+        } else if value > *third {
+            *third = value;
+        }
+    }
+
+    #[cfg(all(feature = "asm", target_arch = "x86_64"))]
+    fn try_insert_asm(&mut self, value: u64) {
+        // It's obviously a little silly to do this in asm, but the problem is so constrained that
+        // I felt like I could make it go quite fast.
+        //
+        // ... Does this count as doing AoC in assembly, or...?
+        unsafe {
+            asm!("mov cl, 0",
+                 //"0:", // ---> Compare rax with [rdi]
+                 "cmp rax, [rdi]",
+                 "jb 2f",
+                 "add cl, 1", // Set the LSb of cl to signal we should put rax into [rdi]
+                 "jmp 20f",
+                 "2:", // ---> Compare rax with rdi[1]
+                 "cmp rax, [rdi+8]",
+                 "jb 3f",
+                 "add cl, 2", // Set the second LSb of cl to signal we should put rax into rdi[1]
+                 "jmp 20f",
+                 "3:", // ---> Compare rax with rdi[2]
+                 "cmp rax, [rdi+16]",
+                 "jbe 22f",
+                 "mov [rdi+16], rax",
+                 "jmp 22f",
+                 "add cl, 1",
+                 "20:", // ---> Shift rdi[1] to rdi[2]
+                 "mov rdx, [rdi+8]",
+                 "mov [rdi+16], rdx",
+                 "test cl, 2", // If the second bit of cl is set, put rax into rdi[1]
+                 "jz 21f",
+                 "mov [rdi+8], rax",
+                 "jmp 22f",
+                 "21:", // ---> Shift rdi[0] to rdi[1]
+                 "mov rdx, [rdi]",
+                 "mov [rdi+8], rdx",
+                 "test cl, 1",
+                 "jz 22f", // If the first bit of cl is set, put rax into [rdi]
+                 "mov [rdi], rax",
+                 "22:", // ---> End
+                in("rdi") self.values.as_mut_ptr(), in("rax") value, out("cl") _, out("rdx") _)
+        }
+    }
+
+    fn most(&self) -> &u64 {
+        &self.values[0]
+    }
+
+    /// The three summed, unless that overflows.
+    fn total(&self) -> Option<u64> {
+        let [first, second, third] = self.values;
+        first.checked_add(second)?.checked_add(third)
+    }
+}
+
+/// Sums each blank-line-separated block of calories, passing the total carried by each elf, and
+/// where the elf is in the input, to `elf` as soon as the block ends. Nothing else is kept, so
+/// that the input can be as large as it likes.
+fn for_each_elf(input: impl Read, elf: impl FnMut(u64, Elf)) -> Result<()> {
+    sum_elves(BufReader::new(input).lines(), elf)
+}
+
+/// `for_each_elf`, over lines that have already been split.
+fn sum_elves<L: AsRef<str>>(
+    lines: impl Iterator<Item = io::Result<L>>,
+    mut elf: impl FnMut(u64, Elf),
+) -> Result<()> {
+    let mut elves = 0;
+    // The block so far: its total, and its first and last lines.
+    let mut acc: Option<(u64, usize, usize)> = None;
+    let mut finish = |acc: Option<(u64, usize, usize)>| {
+        if let Some((calories, first, last)) = acc {
+            elves += 1;
+            let (index, lines) = (elves, first..=last);
+            elf(calories, Elf { index, lines });
+        }
+    };
+
+    for (line_no, line) in lines.enumerate() {
+        let line = line?;
+        let line = line.as_ref();
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            // At the end of a block.
+            finish(acc.take());
+            continue;
+        }
+
+        let column = line.len() - line.trim_start().len() + 1;
+        let error = |message| ParseError::new(message, line_no + 1, column, line);
+        let parsed =
+            str::parse::<u64>(trimmed).map_err(|_| error("expected a number of calories"))?;
+        let (calories, _, last) = acc.get_or_insert((0, line_no + 1, line_no + 1));
+        *calories = calories
+            .checked_add(parsed)
+            .ok_or_else(|| error("this elf's calories add up to more than 2^64 - 1"))?;
+        *last = line_no + 1;
+    }
+    finish(acc);
+
+    Ok(())
+}
+
+/// Every elf's total, and where the elf is in the input.
+fn elves(input: impl Read) -> Result<Vec<(u64, Elf)>> {
+    let mut elves = vec![];
+    for_each_elf(input, |calories, elf| elves.push((calories, elf)))?;
+    Ok(elves)
+}
+
+/// Keeps the three largest totals.
+fn top_three(input: impl Read) -> Result<TopThree> {
+    let mut top_three = TopThree::new();
+    for_each_elf(input, |calories, _| top_three.try_insert(calories))?;
+    Ok(top_three)
+}
+
+#[test]
+fn exampleinput() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part1(input).unwrap(), 24000);
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part2(input).unwrap(), 45000);
+}
+
+#[test]
+fn top_three_matches_sorting() {
+    use aoc_check::{ints, vecs, Check};
+
+    let calories = vecs(ints(0, u64::MAX), 0);
+    Check::new().differential(
+        &calories,
+        |calories| {
+            let mut top_three = TopThree::new();
+            for &c in calories {
+                top_three.try_insert(c);
+            }
+            top_three.values
+        },
+        |calories| {
+            let mut sorted = calories.clone();
+            sorted.sort_unstable_by(|a, b| b.cmp(a));
+            sorted.resize(sorted.len().max(3), 0);
+            [sorted[0], sorted[1], sorted[2]]
+        },
+    );
+}
+
+#[cfg(all(feature = "asm", target_arch = "x86_64"))]
+#[test]
+fn asm_matches_portable() {
+    use aoc_check::{ints, vecs, Check};
+
+    // Small values, so that there are plenty of ties.
+    let calories = vecs(ints(0, 10), 0);
+    let top_three = |calories: &Vec<u64>, try_insert: fn(&mut TopThree, u64)| {
+        let mut top_three = TopThree::new();
+        for &c in calories {
+            try_insert(&mut top_three, c);
+        }
+        top_three.values
+    };
+    Check::new().differential(
+        &calories,
+        |calories| top_three(calories, TopThree::try_insert_asm),
+        |calories| top_three(calories, TopThree::try_insert_portable),
+    );
+}
+
+#[test]
+fn ranked_elves() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    let leaderboard = leaderboard(input, 2).unwrap();
+    let ranked: Vec<_> = leaderboard
+        .iter()
+        .map(|ranked| (ranked.value, ranked.elf.index, ranked.elf.lines.clone()))
+        .collect();
+    assert_eq!(ranked, [(24000, 4, 10..=12), (11000, 3, 7..=8)]);
+}
+
+#[test]
+fn malformed() {
+    let error = solve_part1("1000\n\n20x0\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (3, 1));
+
+    let max = u64::MAX;
+    let error = solve_part1(format!("1\n\n{}\n  1\n", max).as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (4, 3));
+    let input = format!("{}\n\n{}\n\n{}\n", max, max, max);
+    assert_eq!(solve_part1(input.as_bytes()).unwrap(), max);
+    let error = solve_part2(input.as_bytes()).unwrap_err();
+    assert!(matches!(error, Error::Overflow(_)), "{}", error);
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub fn parse(input: impl Read) -> Result<impl Sized> {
+    elves(input)
+}
+
+/// The `k` elves carrying the most calories, most first.
+pub fn leaderboard(input: impl Read, k: usize) -> Result<Top<u64>> {
+    let mut top = Top::new(k);
+    for_each_elf(input, |calories, elf| top.insert(calories, elf))?;
+    Ok(top)
+}
+
+/// Statistics over every elf's total, gathered in a single pass over the input.
+pub fn statistics(input: impl Read) -> Result<Statistics> {
+    let mut statistics = Statistics::new();
+    for_each_elf(input, |calories, _| statistics.add(calories))?;
+    Ok(statistics)
+}
+
+/// The most calories carried by a single elf.
+pub fn solve_part1(input: impl Read) -> Result<u64> {
+    Ok(*top_three(input)?.most())
+}
+
+/// The calories carried by the top three elves, in total.
+pub fn solve_part2(input: impl Read) -> Result<u64> {
+    top_three(input)?.total().ok_or_else(top_three_overflow)
+}
+
+fn top_three_overflow() -> Error {
+    Error::Overflow("the top three's calories add up to more than 2^64 - 1".to_string())
+}
@@ -1,90 +1,121 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::arch::asm;
+use aoc_core::{InFile, Input, Report};
+use day1::Chunked;
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::Path,
+    str::FromStr,
+};
 
-struct TopThree {
-    values: [u32; 3],
-}
+const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
 
-impl TopThree {
-    fn new() -> Self {
-        TopThree {
-            values: [0; 3],
+/// The value given with the `option`, if any, parsed; `what` it should be is for the error.
+fn option<T: FromStr>(option: &str, what: &str) -> io::Result<Option<T>> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == option {
+            let value = args.next().unwrap_or_default();
+            return match value.parse() {
+                Ok(value) => Ok(Some(value)),
+                Err(_) => {
+                    let message = format!("{:?} is not {}", value, what);
+                    Err(io::Error::new(io::ErrorKind::InvalidInput, message))
+                }
+            };
         }
     }
+    Ok(None)
+}
 
-    fn try_insert(&mut self, value: u32) {
-        // It's obviously a little silly to do this in asm, but the problem is so constrained that
-        // I felt like I could make it go quite fast.
-        // 
-        // ... Does this count as doing AoC in assembly, or...?
-        unsafe {
-            asm!("mov cl, 0",
-                 //"0:", // ---> Compare eax with [rdi]
-                 "cmp eax, [rdi]",
-                 "jl 1f",
-                 "add cl, 1", // Set the LSb of cl to signal we should put eax into [rdi]
-                 "jmp 10f",
-                 "1:", // ---> Compare eax with rdi[1]
-                 "cmp eax, [rdi+4]",
-                 "jl 2f",
-                 "add cl, 2", // Set the second LSb of cl to signal we should put eax into rdi[1]
-                 "jmp 10f",
-                 "2:", // ---> Compare eax with rdi[2]
-                 "cmp eax, [rdi+8]",
-                 "jle 12f",
-                 "mov [rdi+8], eax",
-                 "jmp 12f",
-                 "add cl, 1",
-                 "10:", // ---> Shift rdi[1] to rdi[2]
-                 "mov edx, [rdi+4]",
-                 "mov [rdi+8], edx",
-                 "test cl, 2", // If the second bit of cl is set, put eax into rdi[1]
-                 "jz 11f",
-                 "mov [rdi+4], eax",
-                 "jmp 12f",
-                 "11:", // ---> Shift rdi[0] to rdi[1]
-                 "mov edx, [rdi]",
-                 "mov [rdi+4], edx",
-                 "test cl, 1",
-                 "jz 12f", // If the first bit of cl is set, put eax into [rdi]
-                 "mov [rdi], eax",
-                 "12:", // ---> End
-                in("rdi") self.values.as_mut_ptr(), in("eax") value, out("cl") _, out("edx") _)
+/// The percentiles given with `--stats`, if any, e.g. `--stats 90,99,99.9`.
+fn stats_arg() -> io::Result<Option<Vec<f64>>> {
+    let Some(value) = option::<String>("--stats", "a list of percentiles")? else {
+        return Ok(None);
+    };
+    let percentiles = value
+        .split(',')
+        .filter(|p| !p.is_empty())
+        .map(|p| p.parse().ok().filter(|p| (0.0..=100.0).contains(p)))
+        .collect::<Option<_>>();
+    match percentiles {
+        Some(percentiles) => Ok(Some(percentiles)),
+        None => {
+            let message = format!("{:?} is not a list of percentiles", value);
+            Err(io::Error::new(io::ErrorKind::InvalidInput, message))
         }
     }
+}
 
-    fn most(&self) -> &u32 {
-        &self.values[0]
-    }
-
-    fn total(&self) -> u32 {
-        self.values.iter().sum()
+/// The input, to be read a bit at a time rather than all at once.
+fn open(source: &Path) -> io::Result<Box<dyn Read>> {
+    if source == Path::new("-") {
+        return Ok(Box::new(io::stdin().lock()));
     }
+    let file = fs::File::open(source).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("could not read {}: {}", source.display(), e),
+        )
+    })?;
+    Ok(Box::new(file))
 }
 
-fn main() {
-    let readin =
-        BufReader::new(File::open("input").expect("the input file to exist and be readable"));
-
-    let mut top_three = TopThree::new();
-    let mut acc = 0;
+fn main() -> aoc_core::Result<()> {
+    if let Some(threads) = option::<usize>("--threads", "a number of threads")? {
+        // The input could be too large to read in full, and standard input can only be read once,
+        // so a single pass keeps the top three for both parts.
+        let chunked = Chunked {
+            threads: threads.max(1),
+            ..Chunked::default()
+        };
+        let source = Input::source_from_args(DEFAULT_INPUT);
+        let name = source.display().to_string();
+        let report = Report::from_args(1)?;
+        let mut top = None;
+        report.part(1, "Most calories: {}", || {
+            let top = top.insert(chunked.leaderboard(open(&source)?, 3).in_file(&name)?);
+            Ok(Chunked::most(top))
+        })?;
+        let top = top.expect("part 1 to have kept the top three");
+        report.part(2, "Top three total: {}", || {
+            Chunked::top_three_total(&top).in_file(&name)
+        })?;
+        return Ok(());
+    }
 
-    for line in readin.lines() {
-        let line = line.unwrap();
-        let trimmed = line.trim();
+    let input = Input::from_args(DEFAULT_INPUT)?;
 
-        if trimmed.len() == 0 {
-            // At the end of a block.
-            top_three.try_insert(acc);
-            acc = 0;
-            continue;
+    if let Some(k) = option("--leaderboard", "a number of elves")? {
+        let leaderboard = day1::leaderboard(input.as_bytes(), k).in_file(input.name())?;
+        println!("Rank  Elf    Lines        Calories");
+        for (rank, ranked) in leaderboard.iter().enumerate() {
+            let lines = format!("{}-{}", ranked.elf.lines.start(), ranked.elf.lines.end());
+            println!(
+                "{:>4}  {:<5}  {:<11}  {:>8}",
+                rank + 1,
+                ranked.elf.index,
+                lines,
+                ranked.value
+            );
         }
+        // Summed wider than the totals, which could overflow otherwise.
+        let total: u128 = leaderboard.iter().map(|ranked| ranked.value as u128).sum();
+        println!("Total: {}", total);
+        return Ok(());
+    }
 
-        let parsed = str::parse::<u32>(trimmed).unwrap();
-        acc += parsed;
+    if let Some(percentiles) = stats_arg()? {
+        let statistics = day1::statistics(input.as_bytes()).in_file(input.name())?;
+        println!("{}", statistics.report(&percentiles));
+        return Ok(());
     }
-    top_three.try_insert(acc);
 
-    println!("Most calories: {}\nTop three total: {}", top_three.most(), top_three.total());
+    let report = Report::from_args(1)?;
+    report.part(1, "Most calories: {}", || {
+        day1::solve_part1(input.as_bytes()).in_file(input.name())
+    })?;
+    report.part(2, "Top three total: {}", || {
+        day1::solve_part2(input.as_bytes()).in_file(input.name())
+    })?;
+    Ok(())
 }
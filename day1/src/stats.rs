@@ -0,0 +1,267 @@
+use std::{fmt, ops::RangeInclusive};
+
+/// How many bits of each total the buckets keep: totals below `2 << BITS` get a bucket of their
+/// own, and larger ones share theirs with totals less than 1/64th (`2^-BITS`) away.
+const BITS: u32 = 6;
+const EXACT: u64 = 2 << BITS;
+
+/// Statistics over the elves' totals, gathered in a single pass without keeping the totals: the
+/// median and percentiles come from a histogram with buckets a fixed fraction wide, so that they
+/// are exact for small totals and within 1/64th for the rest.
+#[derive(Debug, Clone)]
+pub struct Statistics {
+    count: u64,
+    sum: u128,
+    min: u64,
+    max: u64,
+    buckets: Vec<u64>,
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Statistics {
+    pub fn new() -> Self {
+        Statistics {
+            count: 0,
+            sum: 0,
+            min: u64::MAX,
+            max: 0,
+            buckets: vec![0; bucket(u64::MAX) + 1],
+        }
+    }
+
+    pub fn add(&mut self, total: u64) {
+        self.count += 1;
+        self.sum += u128::from(total);
+        self.min = self.min.min(total);
+        self.max = self.max.max(total);
+        self.buckets[bucket(total)] += 1;
+    }
+
+    /// Adds in the totals gathered by `other`.
+    pub fn merge(&mut self, other: &Statistics) {
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        for (bucket, count) in self.buckets.iter_mut().zip(&other.buckets) {
+            *bucket += count;
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn sum(&self) -> u128 {
+        self.sum
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    pub fn max(&self) -> Option<u64> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum as f64 / self.count as f64)
+    }
+
+    /// The range the `p`th percentile (by nearest rank) lies in; a single total if it's known
+    /// exactly.
+    pub fn percentile(&self, p: f64) -> Option<RangeInclusive<u64>> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = ((p.clamp(0.0, 100.0) / 100.0 * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, &count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                let bounds = bounds(index);
+                let low = (*bounds.start()).max(self.min);
+                let high = (*bounds.end()).min(self.max);
+                return Some(low..=high);
+            }
+        }
+        unreachable!("the buckets to hold every total")
+    }
+
+    pub fn median(&self) -> Option<RangeInclusive<u64>> {
+        self.percentile(50.0)
+    }
+
+    /// How many totals fall in each quarter of a power of two (e.g. 1024-1279, 1280-1535, ...),
+    /// from the minimum's to the maximum's.
+    pub fn histogram(&self) -> Vec<(RangeInclusive<u64>, u64)> {
+        let (Some(min), Some(max)) = (self.min(), self.max()) else {
+            return vec![];
+        };
+        let first = bin(min);
+        let mut histogram: Vec<_> = (first..=bin(max)).map(|bin| (bin_bounds(bin), 0)).collect();
+        // The buckets don't straddle bins, so each can be counted by its lowest total.
+        for (index, &count) in self.buckets.iter().enumerate() {
+            if count > 0 {
+                histogram[bin(*bounds(index).start()) - first].1 += count;
+            }
+        }
+        histogram
+    }
+}
+
+/// The histogram's bin for the total: its own below 4, and a quarter of its power of two above.
+fn bin(total: u64) -> usize {
+    if total < 4 {
+        return total as usize;
+    }
+    let power = total.ilog2();
+    let quarter = (total >> (power - 2)) & 3;
+    4 + (power as usize - 2) * 4 + quarter as usize
+}
+
+fn bin_bounds(bin: usize) -> RangeInclusive<u64> {
+    if bin < 4 {
+        return bin as u64..=bin as u64;
+    }
+    let shift = (bin - 4) / 4;
+    let low = (4 + (bin as u64 - 4) % 4) << shift;
+    low..=low + ((1 << shift) - 1)
+}
+
+fn bucket(total: u64) -> usize {
+    if total < EXACT {
+        return total as usize;
+    }
+    let shift = total.ilog2() - BITS;
+    let mantissa = (total >> shift) as usize;
+    EXACT as usize + (shift as usize - 1) * (1 << BITS) + (mantissa - (1 << BITS))
+}
+
+/// The totals that fall in the bucket.
+fn bounds(index: usize) -> RangeInclusive<u64> {
+    if index < EXACT as usize {
+        return index as u64..=index as u64;
+    }
+    let index = index - EXACT as usize;
+    let shift = (index >> BITS) as u32 + 1;
+    let mantissa = ((1 << BITS) + (index & ((1 << BITS) - 1))) as u64;
+    let low = mantissa << shift;
+    low..=low + ((1 << shift) - 1)
+}
+
+/// Shows a percentile, or the middle of its range (marked as approximate).
+fn approximately(range: RangeInclusive<u64>) -> String {
+    match range.start() == range.end() {
+        true => range.start().to_string(),
+        false => format!("~{}", range.start() + (range.end() - range.start()) / 2),
+    }
+}
+
+impl Statistics {
+    /// Every statistic, with the given percentiles, as text.
+    pub fn report(&self, percentiles: &[f64]) -> String {
+        let mut report = String::new();
+        self.write_report(&mut report, percentiles)
+            .expect("writing to a string to succeed");
+        report
+    }
+
+    fn write_report(&self, f: &mut impl fmt::Write, percentiles: &[f64]) -> fmt::Result {
+        let (Some(min), Some(max), Some(mean)) = (self.min(), self.max(), self.mean()) else {
+            return write!(f, "Elves: 0");
+        };
+        writeln!(f, "Elves: {}", self.count)?;
+        writeln!(f, "Total: {}", self.sum)?;
+        writeln!(f, "Mean: {:.1}", mean)?;
+        writeln!(f, "Min: {}", min)?;
+        writeln!(f, "Max: {}", max)?;
+        writeln!(
+            f,
+            "Median: {}",
+            approximately(self.median().expect("some totals"))
+        )?;
+        for &p in percentiles {
+            let percentile = self.percentile(p).expect("some totals");
+            writeln!(f, "p{}: {}", p, approximately(percentile))?;
+        }
+
+        write!(f, "Histogram:")?;
+        let histogram = self.histogram();
+        let most = histogram.iter().map(|&(_, count)| count).max().unwrap_or(0);
+        let label = |range: &RangeInclusive<u64>| format!("{}-{}", range.start(), range.end());
+        let width = histogram.iter().map(|(range, _)| label(range).len()).max();
+        for (range, count) in &histogram {
+            // Bars of up to 40 characters, with any count at all getting one.
+            let bar = (count * 40).div_ceil(most.max(1)) as usize;
+            write!(
+                f,
+                "\n  {:>width$} | {} {}",
+                label(range),
+                "#".repeat(bar),
+                count,
+                width = width.unwrap_or(0)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_report(f, &[10.0, 25.0, 75.0, 90.0, 99.0])
+    }
+}
+
+#[test]
+fn buckets() {
+    for total in (0..100_000).chain([u64::MAX - 1, u64::MAX, 1 << 40, (1 << 40) - 1]) {
+        let index = bucket(total);
+        assert!(
+            bounds(index).contains(&total),
+            "{} in {:?}",
+            total,
+            bounds(index)
+        );
+        // Within 1/64th of every total in the bucket.
+        let bounds = bounds(index);
+        assert!((bounds.end() - bounds.start()) <= bounds.start() >> BITS);
+    }
+    assert_eq!(*bounds(bucket(u64::MAX)).end(), u64::MAX);
+
+    for total in (0..10_000).chain([u64::MAX, 1 << 63]) {
+        assert!(bin_bounds(bin(total)).contains(&total), "{}", total);
+    }
+    assert_eq!(bin_bounds(bin(1100)), 1024..=1279);
+}
+
+#[test]
+fn matches_sorting() {
+    use aoc_check::{ints, vecs, Check};
+
+    Check::new().property(&vecs(ints(0, 10_000), 1), |totals| {
+        let mut statistics = Statistics::new();
+        for &total in totals {
+            statistics.add(total);
+        }
+        let mut sorted = totals.clone();
+        sorted.sort();
+        for p in [1.0, 10.0, 50.0, 90.0, 100.0] {
+            let rank = ((p / 100.0 * sorted.len() as f64).ceil() as usize).max(1);
+            let percentile = statistics.percentile(p).unwrap();
+            if !percentile.contains(&sorted[rank - 1]) {
+                return Err(format!("percentile {} in {:?}", p, percentile));
+            }
+        }
+        let histogram = statistics.histogram();
+        if histogram.iter().map(|&(_, count)| count).sum::<u64>() != totals.len() as u64 {
+            return Err(format!("histogram {:?}", histogram));
+        }
+        Ok(())
+    });
+}
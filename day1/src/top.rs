@@ -0,0 +1,161 @@
+use std::ops::{Deref, DerefMut, RangeInclusive};
+
+/// Where an elf's block of calories is in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    /// Counting from 1, in the order of the input.
+    pub index: usize,
+    /// The block's lines, counting from 1.
+    pub lines: RangeInclusive<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ranked<T> {
+    pub value: T,
+    pub elf: Elf,
+}
+
+/// The `k` largest values inserted, largest first, with the elves they came from. Of equal
+/// values, the one inserted first ranks higher.
+#[derive(Debug, Clone)]
+pub struct Top<T> {
+    k: usize,
+    ranked: Vec<Ranked<T>>,
+}
+
+impl<T: Ord> Top<T> {
+    pub fn new(k: usize) -> Self {
+        // Not allocating `k` up front, which could be far more than there are elves (or memory).
+        Top { k, ranked: vec![] }
+    }
+
+    pub fn insert(&mut self, value: T, elf: Elf) {
+        let rank = self.ranked.partition_point(|ranked| ranked.value >= value);
+        if rank < self.k {
+            if self.ranked.len() == self.k {
+                self.ranked.pop();
+            }
+            self.ranked.insert(rank, Ranked { value, elf });
+        }
+    }
+
+    /// The largest value, unless nothing was inserted.
+    pub fn most(&self) -> Option<&Ranked<T>> {
+        self.ranked.first()
+    }
+
+    /// The kept values, in rank order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Ranked<T>> {
+        self.ranked.iter()
+    }
+}
+
+impl Top<u64> {
+    /// The kept values, summed, unless that overflows.
+    pub fn total(&self) -> Option<u64> {
+        (self.ranked.iter()).try_fold(0u64, |total, ranked| total.checked_add(ranked.value))
+    }
+}
+
+/// `Top` with `k` fixed at compile time.
+#[derive(Debug, Clone)]
+pub struct TopK<const K: usize, T>(Top<T>);
+
+impl<const K: usize, T: Ord> TopK<K, T> {
+    pub fn new() -> Self {
+        TopK(Top::new(K))
+    }
+}
+
+impl<const K: usize, T: Ord> Default for TopK<K, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const K: usize, T> Deref for TopK<K, T> {
+    type Target = Top<T>;
+
+    fn deref(&self) -> &Top<T> {
+        &self.0
+    }
+}
+
+impl<const K: usize, T> DerefMut for TopK<K, T> {
+    fn deref_mut(&mut self) -> &mut Top<T> {
+        &mut self.0
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Top<T> {
+    type Item = &'a Ranked<T>;
+    type IntoIter = std::slice::Iter<'a, Ranked<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranked.iter()
+    }
+}
+
+#[test]
+fn matches_sorting() {
+    use aoc_check::{ints, vecs, Check};
+    use std::cmp::Reverse;
+
+    // Small values, so that there are plenty of ties.
+    let cases = (vecs(ints(0, 10), 0), ints(0, 6));
+    let elf = |index| Elf {
+        index,
+        lines: index..=index,
+    };
+    Check::new().differential(
+        &cases,
+        |(values, k)| {
+            let mut top = Top::new(*k as usize);
+            for (i, &value) in values.iter().enumerate() {
+                top.insert(value, elf(i + 1));
+            }
+            top.iter().cloned().collect::<Vec<_>>()
+        },
+        |(values, k)| {
+            let mut ranked: Vec<_> = (values.iter().enumerate())
+                .map(|(i, &value)| Ranked {
+                    value,
+                    elf: elf(i + 1),
+                })
+                .collect();
+            // Stable, so that ties stay in the order they came in.
+            ranked.sort_by_key(|ranked| Reverse(ranked.value));
+            ranked.truncate(*k as usize);
+            ranked
+        },
+    );
+
+    let mut top_two = TopK::<2, u64>::new();
+    assert!(top_two.most().is_none());
+    for (i, value) in [5, 9, 7].into_iter().enumerate() {
+        top_two.insert(value, elf(i + 1));
+    }
+    assert_eq!(top_two.most().unwrap().elf.index, 2);
+    assert_eq!(top_two.total(), Some(16));
+}
+
+#[test]
+fn huge_k() {
+    let mut top = Top::new(usize::MAX);
+    top.insert(
+        1,
+        Elf {
+            index: 1,
+            lines: 1..=1,
+        },
+    );
+    assert_eq!(top.total(), Some(1));
+    top.insert(
+        u64::MAX,
+        Elf {
+            index: 2,
+            lines: 3..=3,
+        },
+    );
+    assert_eq!(top.total(), None);
+}
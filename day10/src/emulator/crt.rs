@@ -1,10 +1,15 @@
 use super::{Cpu, Program};
-use itertools::Itertools;
 
 pub struct Crt {
     buffer: String,
 }
 
+impl Default for Crt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Crt {
     pub fn new() -> Self {
         Crt {
@@ -22,12 +27,13 @@ impl Crt {
         }
     }
 
+    /// The pixels drawn so far, 40 to a row; the last row stops short if the program did.
     pub fn buffer(self) -> String {
-        let lines = (self.buffer.len() - 1) / 40 + 1;
-        (1..=lines).map(|i| &self.buffer[(i - 1) * 40..i * 40]).intersperse("\n").collect()
-    }
-
-    pub fn print_line(&self) {
-        println!("{}", &self.buffer[((self.buffer.len() - 1) / 40) * 40..])
+        let pixels: Vec<char> = self.buffer.chars().collect();
+        pixels
+            .chunks(40)
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
@@ -42,29 +42,35 @@ impl<P: Program> BlockingDispatcher<P> {
     /// Polls the CPU command at the **start** of each tick.
     ///
     /// For example, the following program
-    ///     
-    ///     noop
-    ///     noop
-    ///     addx 1
+    ///
+    /// ```text
+    /// noop
+    /// noop
+    /// addx 1
+    /// ```
     ///
     /// will yield the following `poll` call results:
     ///
-    ///     BlockingState::Completed(Command::NoOp) // Second tick: noop has finished
-    ///                                             // and next noop has begun dispatching
-    ///     BlockingState::Completed(Command::NoOp) // Third tick: noop has finished and
-    ///                                             // and addx has begun dispatching
-    ///     BlockingState::Blocked  // Third tick: addx is blocking
-    ///     BlockingState::Completed(Command::AddX(1)) // Fourth tick: addx completed
-    ///     
+    /// ```text
+    /// BlockingState::Completed(Command::NoOp) // Second tick: noop has finished
+    ///                                         // and next noop has begun dispatching
+    /// BlockingState::Completed(Command::NoOp) // Third tick: noop has finished and
+    ///                                         // and addx has begun dispatching
+    /// BlockingState::Blocked  // Third tick: addx is blocking
+    /// BlockingState::Completed(Command::AddX(1)) // Fourth tick: addx completed
+    /// ```
+    ///
     /// Note how the first call to `tick` immediately places us at the start of the *second* tick.
     /// I.e., in the following schematic
     ///
-    ///         [ noop ] [ noop ] [ noop ] [ addx ]
-    ///                  ^
-    ///                  
+    /// ```text
+    ///     [ noop ] [ noop ] [ noop ] [ addx ]
+    ///              ^
+    /// ```
+    ///
     /// Calling `tick` returns the state at the time indicated by `^`.
     pub fn tick(&mut self) -> Option<BlockingState> {
-        let result = if self.blocking > 0 {
+        if self.blocking > 0 {
             self.blocking -= 1;
             Some(BlockingState::Blocked)
         } else {
@@ -75,8 +81,7 @@ impl<P: Program> BlockingDispatcher<P> {
             }
             let done = self.load_cmd(next_cmd.unwrap_or(Command::NoOp)); // Will set `next` and `blocking`
             Some(BlockingState::Completed(done))
-        };
-        result
+        }
     }
 
     fn load_cmd(&mut self, cmd: Command) -> Command {
@@ -84,7 +89,6 @@ impl<P: Program> BlockingDispatcher<P> {
             Command::AddX(_) => 1, // Will block next tick
             Command::NoOp => 0,    // Will be dispatched next tick
         };
-        let replaced = std::mem::replace(&mut self.next, cmd);
-        replaced
+        std::mem::replace(&mut self.next, cmd)
     }
 }
@@ -0,0 +1,24 @@
+use aoc_core::Rng;
+
+/// A random program of about `instructions` instructions, running for a whole number of rows of
+/// the CRT (and at least the six rows of the screen).
+pub fn generate(rng: &mut Rng, instructions: usize) -> String {
+    let cycles = (instructions * 3 / 2).max(240).next_multiple_of(40);
+    let mut input = String::new();
+    let mut x: i64 = 1;
+    let mut cycle = 0;
+    while cycle < cycles {
+        if cycles - cycle == 1 || rng.one_in(3) {
+            input.push_str("noop\n");
+            cycle += 1;
+        } else {
+            // Keep the sprite around the screen.
+            let to = rng.between(0, 40) as i64 - 1;
+            let v = (to - x).clamp(-15, 15);
+            x += v;
+            input.push_str(&format!("addx {}\n", v));
+            cycle += 2;
+        }
+    }
+    input
+}
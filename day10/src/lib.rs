@@ -0,0 +1,127 @@
+pub mod emulator;
+mod generate;
+pub mod parser;
+
+pub use generate::generate;
+
+use aoc_core::Result;
+use std::io::{self, Read};
+
+#[test]
+fn noparsing() {
+    // [ noop ] [ addx ] [... ] [ addx ] [ ... ]
+    // 0        1        2      3        4
+    let program = [
+        emulator::Command::NoOp,
+        emulator::Command::AddX(2),
+        emulator::Command::AddX(-1),
+    ]
+    .into_iter();
+    let mut cpu = emulator::Cpu::load(program);
+    while !cpu.done() {
+        println!("{}:{}", cpu.clock().count_finished(), cpu.x_register().value());
+        cpu.finish_frame();
+    }
+    println!("{}:{}", cpu.clock().count_finished(), cpu.x_register().value());
+    assert_eq!(cpu.x_register().value(), 1 + 2 - 1);
+}
+
+#[test]
+fn onlyparsing() {
+    let input = std::fs::read_to_string("input").unwrap();
+    let program = parser::parse_program(input.as_bytes()).unwrap();
+    assert_eq!(program.count(), input.lines().count());
+}
+
+#[test]
+fn exampleinput() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part1(input).unwrap(), 13140)
+}
+
+#[test]
+fn malformed() {
+    let error = solve_part1("noop\naddx 3\naddx\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (3, 5));
+
+    let error = solve_part1("noop\n  addx three\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (2, 8));
+
+    let error = solve_part1("noop\nmul 2\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (2, 1));
+}
+
+fn get_interesting_signal(program: impl emulator::Program) -> isize {
+    let mut cpu = emulator::Cpu::load(program);
+
+    let mut answer_acc = 0;
+
+    // "Warm up" the first 19 cycles
+    for _ in 0..19 {
+        cpu.finish_frame();
+    }
+
+    while !cpu.done() {
+        let current_tick = cpu.clock().current_frame();
+        if current_tick > 220 {
+            break;
+        }
+        if (current_tick - 20).is_multiple_of(40) {
+            answer_acc += cpu.x_register().value() * current_tick as isize;
+        }
+        cpu.finish_frame();
+    }
+
+    answer_acc
+}
+
+#[test]
+fn examplecrt() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(
+        solve_part2(input).unwrap(),
+        "##..##..##..##..##..##..##..##..##..##..\n\
+         ###...###...###...###...###...###...###.\n\
+         ####....####....####....####....####....\n\
+         #####.....#####.....#####.....#####.....\n\
+         ######......######......######......####\n\
+         #######.......#######.......#######....."
+    );
+}
+
+#[test]
+fn partialcrt() {
+    assert_eq!(solve_part2("noop\n".as_bytes()).unwrap(), "#");
+    let program = "addx 15\n".repeat(21);
+    assert_eq!(solve_part2(program.as_bytes()).unwrap().lines().count(), 2);
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub fn parse(input: impl Read) -> Result<impl Sized> {
+    parser::parse_program(input)
+}
+
+/// The sum of the signal strengths during the 20th, 60th, ... and 220th cycles.
+pub fn solve_part1(input: impl Read) -> Result<isize> {
+    let input = io::read_to_string(input)?;
+    let program = parser::parse_program(input.as_bytes())?;
+    Ok(get_interesting_signal(program))
+}
+
+/// The image drawn on the CRT, one line per row of pixels.
+pub fn solve_part2(input: impl Read) -> Result<String> {
+    let input = io::read_to_string(input)?;
+    let program = parser::parse_program(input.as_bytes())?;
+    let mut cpu = emulator::Cpu::load(program);
+    let mut crt = emulator::crt::Crt::new();
+
+    while !cpu.done() {
+        crt.read(&cpu);
+        cpu.finish_frame();
+    }
+
+    Ok(crt.buffer())
+}
@@ -1,48 +1,59 @@
 use crate::emulator::{Command, Program};
-use std::{
-    fs::File,
-    io::{BufRead, BufReader, Lines, Read},
-    path::Path,
-};
+use aoc_core::{ParseError, Result};
+use aoc_parse::{Parser, Reader, Source};
+use std::io::{BufReader, Read};
 
 pub struct ParsedProgram<R: Read> {
-    inner: Lines<BufReader<R>>,
+    parser: Parser<Reader<BufReader<R>>>,
 }
 
 impl<R: Read> ParsedProgram<R> {
     fn new(inner: BufReader<R>) -> Self {
         ParsedProgram {
-            inner: inner.lines(),
+            parser: Parser::from_reader(inner),
         }
     }
 }
 
+/// Parses a single instruction, and the end of its line.
+fn command(parser: &mut Parser<impl Source>) -> Result<Command> {
+    let start = parser.position();
+    let opcode = parser.take_while(|c| !c.is_whitespace());
+    let command = match opcode.as_str() {
+        "addx" => {
+            parser.skip_spaces();
+            Command::AddX(parser.signed()?)
+        }
+        "noop" => Command::NoOp,
+        other => {
+            let message = format!("unknown instruction {:?}", other);
+            return Err(parser.error_at(message, start));
+        }
+    };
+    parser.skip_spaces();
+    parser.end_of_line()?;
+    Ok(command)
+}
+
 impl<R: Read> Iterator for ParsedProgram<R> {
-    type Item = Command;
+    type Item = Result<Command>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let line = self.inner.next()?.expect("to be able to read line");
-        let mut words = line.split_whitespace();
-        match words.next()? {
-            "addx" => {
-                let amount = words.next().expect("addx to be followed by a number");
-                let amount = amount
-                    .parse::<isize>()
-                    .expect("word after addx to be a number");
-                if let Some(rest) = words.next() {
-                    panic!("Trailing word after addx command: {}", rest);
-                }
-                Some(Command::AddX(amount))
-            }
-            "noop" => Some(Command::NoOp),
-            other => {
-                panic!("Cannot parse {} command", other)
-            }
+        // Blank lines don't mean anything.
+        self.parser.skip_whitespace();
+        match self.parser.done() {
+            Ok(true) => None,
+            Ok(false) => Some(command(&mut self.parser)),
+            Err(e) => Some(Err(e)),
         }
     }
 }
 
-pub fn parse_program<P: AsRef<Path>>(from: P) -> impl Program {
-    let input = BufReader::new(File::open(from).expect("path to exist and be readable"));
-    ParsedProgram::new(input)
+/// Reads the whole program, failing at the first line that isn't an instruction.
+pub fn parse_program(input: impl Read) -> Result<impl Program> {
+    let commands = ParsedProgram::new(BufReader::new(input)).collect::<Result<Vec<_>>>()?;
+    if commands.is_empty() {
+        return Err(ParseError::new("expected at least one instruction", 1, 1, "").into());
+    }
+    Ok(commands.into_iter())
 }
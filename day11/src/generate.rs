@@ -0,0 +1,130 @@
+use aoc_core::Rng;
+
+/// Distinct, so that the product the worry levels are kept under stays small.
+const DIVIDERS: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Add(u64),
+    Mul(u64),
+    Square,
+}
+
+struct Monkey {
+    operation: Operation,
+    divider: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+/// Random notes on two to nine monkeys, holding `items` items between them (at least one each).
+/// The worry levels stay small enough, without being kept under the dividers, for the 20 rounds
+/// of part 1.
+pub fn generate(rng: &mut Rng, items: usize) -> String {
+    loop {
+        let monkeys = monkeys(rng);
+        let mut holding = vec![vec![]; monkeys.len()];
+        let all_placed = (0..items.max(monkeys.len())).all(|item| {
+            // Some worry levels grow too much; try a few before giving up on these monkeys.
+            (0..100).any(|_| {
+                let monkey = match item < monkeys.len() {
+                    true => item,
+                    false => rng.index(monkeys.len()),
+                };
+                let worry = rng.between(40, 99);
+                let ok = stays_small(&monkeys, monkey, worry);
+                if ok {
+                    holding[monkey].push(worry);
+                }
+                ok
+            })
+        });
+        if all_placed {
+            return describe(&monkeys, &holding);
+        }
+    }
+}
+
+fn monkeys(rng: &mut Rng) -> Vec<Monkey> {
+    let mut dividers = DIVIDERS;
+    rng.shuffle(&mut dividers);
+    let count = rng.between(2, DIVIDERS.len() as u64) as usize;
+    (0..count)
+        .map(|index| {
+            let operation = match rng.below(8) {
+                0..=3 => Operation::Add(rng.between(1, 8)),
+                4..=6 => Operation::Mul(rng.between(2, 19)),
+                _ => Operation::Square,
+            };
+            // Never to itself, which would keep a monkey busy forever.
+            let mut other = || (index + rng.between(1, count as u64 - 1) as usize) % count;
+            Monkey {
+                operation,
+                divider: dividers[index],
+                if_true: other(),
+                if_false: other(),
+            }
+        })
+        .collect()
+}
+
+/// Follows an item for 20 rounds of part 1, checking that its worry level never overflows.
+fn stays_small(monkeys: &[Monkey], mut monkey: usize, mut worry: u64) -> bool {
+    for _round in 0..20 {
+        loop {
+            let Monkey {
+                operation,
+                divider,
+                if_true,
+                if_false,
+            } = monkeys[monkey];
+            let inspected = match operation {
+                Operation::Add(n) => worry.checked_add(n),
+                Operation::Mul(n) => worry.checked_mul(n),
+                Operation::Square => worry.checked_mul(worry),
+            };
+            let Some(inspected) = inspected else {
+                return false;
+            };
+            worry = inspected / 3;
+            let to = if worry.is_multiple_of(divider) {
+                if_true
+            } else {
+                if_false
+            };
+            // Thrown to a monkey yet to have its turn, the item is inspected again this round.
+            let later = to > monkey;
+            monkey = to;
+            if !later {
+                break;
+            }
+        }
+    }
+    true
+}
+
+fn describe(monkeys: &[Monkey], holding: &[Vec<u64>]) -> String {
+    let mut input = String::new();
+    for (index, (monkey, items)) in monkeys.iter().zip(holding).enumerate() {
+        if index > 0 {
+            input.push('\n');
+        }
+        let items: Vec<String> = items.iter().map(u64::to_string).collect();
+        let operation = match monkey.operation {
+            Operation::Add(n) => format!("old + {}", n),
+            Operation::Mul(n) => format!("old * {}", n),
+            Operation::Square => "old * old".to_string(),
+        };
+        input.push_str(&format!(
+            "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    \
+             If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+            index,
+            items.join(", "),
+            operation,
+            monkey.divider,
+            monkey.if_true,
+            monkey.if_false
+        ));
+    }
+    input
+}
@@ -0,0 +1,268 @@
+use aoc_core::{Error, Result};
+use std::{
+    collections::VecDeque,
+    io::{self, Read},
+};
+
+mod generate;
+mod parsing;
+mod partialsort;
+
+pub use generate::generate;
+
+#[derive(Debug)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+}
+
+#[derive(Debug)]
+pub enum RValue {
+    Old,
+    Literal(usize),
+}
+
+#[derive(Debug)]
+pub struct Operation {
+    operator: Operator,
+    rvalue: RValue,
+}
+
+#[derive(Debug)]
+pub struct Test {
+    divider: usize,
+    if_true: usize,
+    if_false: usize,
+}
+
+#[derive(Debug)]
+pub struct Monkey {
+    items: VecDeque<usize>,
+    operation: Operation,
+    test: Test,
+}
+
+impl Operation {
+    fn operate(&self, in_value: usize) -> Result<usize> {
+        let lvalue = in_value;
+        let rvalue = match self.rvalue {
+            RValue::Old => in_value,
+            RValue::Literal(literal) => literal,
+        };
+        match self.operator {
+            Operator::Add => lvalue.checked_add(rvalue).ok_or_else(worry_overflow),
+            Operator::Sub => lvalue
+                .checked_sub(rvalue)
+                .ok_or_else(|| Error::Overflow("a worry level dropped below zero".to_string())),
+            Operator::Mul => lvalue.checked_mul(rvalue).ok_or_else(worry_overflow),
+        }
+    }
+
+    fn operate_under_ring(&self, in_value: usize, ring: usize) -> usize {
+        // Widened, so that neither the sum nor the product of two residues can overflow.
+        let ring = ring as u128;
+        let lvalue = in_value as u128 % ring;
+        let rvalue = match self.rvalue {
+            RValue::Old => lvalue,
+            RValue::Literal(literal) => literal as u128 % ring,
+        };
+        let out_value = match self.operator {
+            Operator::Add => (lvalue + rvalue) % ring,
+            Operator::Sub => (lvalue + ring - rvalue) % ring,
+            Operator::Mul => (lvalue * rvalue) % ring,
+        };
+        out_value as usize
+    }
+}
+
+fn worry_overflow() -> Error {
+    Error::Overflow("a worry level grew past 2^64 - 1".to_string())
+}
+
+impl Test {
+    fn test(&self, in_value: usize) -> usize {
+        if in_value.is_multiple_of(self.divider) {
+            self.if_true
+        } else {
+            self.if_false
+        }
+    }
+}
+
+impl Monkey {
+    fn throw_to(&self, in_value: usize) -> usize {
+        self.test.test(in_value)
+    }
+}
+
+struct MonkeyBusiness {
+    monkeys: Vec<Monkey>,
+    monkey_number: usize,
+    /// The product of every divider, if it fits: worry levels can be kept modulo it.
+    ring: Option<usize>,
+    inspection: Vec<usize>,
+}
+
+impl MonkeyBusiness {
+    fn new(monkeys: Vec<Monkey>) -> Self {
+        let size = monkeys.len();
+        let ring = monkeys
+            .iter()
+            .try_fold(1usize, |acc, m| acc.checked_mul(m.test.divider));
+        MonkeyBusiness {
+            monkeys,
+            monkey_number: size,
+            ring,
+            inspection: vec![0; size],
+        }
+    }
+
+    fn round(&mut self) -> Result<()> {
+        for monkey_index in 0..self.monkey_number {
+            loop {
+                let monkey = &mut self.monkeys[monkey_index];
+                let worry_level = monkey.items.pop_front();
+                if worry_level.is_none() {
+                    break;
+                }
+                let worry_level = monkey.operation.operate(worry_level.unwrap())?;
+                let worry_level = worry_level / 3;
+                let throw_to = monkey.throw_to(worry_level);
+                self.monkeys[throw_to].items.push_back(worry_level);
+                self.inspection[monkey_index] += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn unbounded_round(&mut self) -> Result<()> {
+        let ring = self.ring.ok_or_else(|| {
+            Error::Overflow("the monkeys' dividers multiply to more than 2^64 - 1".to_string())
+        })?;
+        for monkey_index in 0..self.monkey_number {
+            loop {
+                let monkey = &mut self.monkeys[monkey_index];
+                let worry_level = monkey.items.pop_front();
+                if worry_level.is_none() {
+                    break;
+                }
+                let worry_level = monkey.operation.operate_under_ring(worry_level.unwrap(), ring);
+                let throw_to = monkey.throw_to(worry_level);
+                self.monkeys[throw_to].items.push_back(worry_level);
+                self.inspection[monkey_index] += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn monkey_business(&self) -> Result<usize> {
+        let mut inspections = self.inspection.clone();
+        partialsort::partial_sort(&mut inspections, 2);
+        inspections[0].checked_mul(inspections[1]).ok_or_else(|| {
+            Error::Overflow("the level of monkey business is past 2^64 - 1".to_string())
+        })
+    }
+}
+
+#[test]
+fn parsing() {
+    let input = std::fs::read_to_string("exampleinput").unwrap();
+    println!("{:?}", parsing::parse(&input).unwrap());
+}
+
+#[test]
+fn exampleinput1() {
+    let input = std::fs::read_to_string("exampleinput").unwrap();
+    let monkeys = parsing::parse(&input).unwrap();
+    let mut monkey_business = MonkeyBusiness::new(monkeys);
+
+    for _round in 0..20 {
+        monkey_business.round().unwrap();
+    }
+
+    assert_eq!(monkey_business.monkey_business().unwrap(), 10605);
+
+    let monkeys = monkey_business.monkeys;
+    assert_eq!(&monkeys[0].items, &[10, 12, 14, 26, 34]);
+    assert_eq!(&monkeys[1].items, &[245, 93, 53, 199, 115]);
+    assert_eq!(&monkeys[2].items, &[]);
+    assert_eq!(&monkeys[3].items, &[]);
+}
+
+#[test]
+fn exampleinput2() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part2(input).unwrap(), 2713310158);
+}
+
+#[test]
+fn malformed() {
+    let input = std::fs::read_to_string("exampleinput").unwrap();
+
+    let error = solve_part1(input.replacen("old * 19", "old / 19", 1).as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (3, 24));
+
+    let error = solve_part1(input.replacen("Monkey 1", "Monkey 2", 1).as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (8, 8));
+
+    let error = solve_part1(input.replacen("by 23", "by 0", 1).as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (4, 22));
+    assert_eq!(error.message(), "cannot test for divisibility by zero");
+
+    let error = solve_part1(input.replacen("monkey 3", "monkey 4", 1).as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (6, 31));
+    assert_eq!(error.message(), "there is no monkey 4");
+
+    let error = solve_part1(input.replacen("monkey 0", "monkey 1", 1).as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (13, 31));
+    assert_eq!(error.message(), "a monkey cannot throw to itself");
+
+    let sub = input.replacen("old * 19", "old - 100", 1);
+    let error = solve_part1(sub.as_bytes()).unwrap_err();
+    assert!(matches!(error, Error::Overflow(_)), "{error}");
+    assert!(solve_part2(sub.as_bytes()).is_ok());
+
+    let mul = input.replacen("old * 19", "old * 18446744073709551615", 1);
+    let error = solve_part1(mul.as_bytes()).unwrap_err();
+    assert!(matches!(error, Error::Overflow(_)), "{error}");
+
+    let divider = input.replacen("by 23", "by 18446744073709551557", 1);
+    assert!(solve_part1(divider.as_bytes()).is_ok());
+    let error = solve_part2(divider.as_bytes()).unwrap_err();
+    assert!(matches!(error, Error::Overflow(_)), "{error}");
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub fn parse(input: impl Read) -> Result<impl Sized> {
+    Ok(parsing::parse(&io::read_to_string(input)?)?)
+}
+
+/// The level of monkey business after 20 rounds, with worry relief.
+pub fn solve_part1(input: impl Read) -> Result<usize> {
+    let monkeys = parsing::parse(&io::read_to_string(input)?)?;
+    let mut monkey_business = MonkeyBusiness::new(monkeys);
+
+    for _round in 0..20 {
+        monkey_business.round()?;
+    }
+
+    monkey_business.monkey_business()
+}
+
+/// The level of monkey business after 10000 rounds, without worry relief.
+pub fn solve_part2(input: impl Read) -> Result<usize> {
+    let monkeys = parsing::parse(&io::read_to_string(input)?)?;
+    let mut monkey_business = MonkeyBusiness::new(monkeys);
+
+    for _round in 0..10_000 {
+        monkey_business.unbounded_round()?;
+    }
+
+    monkey_business.monkey_business()
+}
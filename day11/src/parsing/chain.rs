@@ -1,9 +1,9 @@
-use nom::{Parser, IResult};
+//! Nom implements `and_then` which takes two parsers, and returns the output of the latter over
+//! the output of the former. However, I often want to do the opposite, where I discard the output
+//! of the former and run the latter over the rest. This module implements that behaviour, via a
+//! function `.chain(other)`, defined for every `Parser`.
 
-/// Nom implements `and_then` which takes two parsers, and returns the output of the latter over
-/// the output of the former. However, I often want to do the opposite, where I discard the output
-/// of the former and run the latter over the rest. This module implements that behaviour, via a
-/// function `.chain(other)`, defined for every `Parser`.
+use nom::{Parser, IResult};
 
 pub struct Chain<F, G, O1> {
     f: F,
@@ -11,7 +11,7 @@ pub struct Chain<F, G, O1> {
     phantom: core::marker::PhantomData<O1>,
 }
 
-impl<'a, I, O1, O2, E, F: Parser<I, O1, E>, G: Parser<I, O2, E>> Parser<I, O2, E>
+impl<I, O1, O2, E, F: Parser<I, O1, E>, G: Parser<I, O2, E>> Parser<I, O2, E>
     for Chain<F, G, O1>
 {
     fn parse(&mut self, i: I) -> IResult<I, O2, E> {
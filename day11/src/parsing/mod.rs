@@ -1,21 +1,22 @@
-/// Here be dragons. I didn't feel like the parsing was the main part of the problem, so I wasn't
-/// too invested in writing really good/flexible code for this; but, obviously, the problem is very
-/// parsing-heavy.
+//! Here be dragons. I didn't feel like the parsing was the main part of the problem, so I wasn't
+//! too invested in writing really good/flexible code for this; but, obviously, the problem is very
+//! parsing-heavy.
 
 use self::chain::ParserChain;
 use crate::{Monkey, Operation, Operator, RValue, Test};
+use aoc_core::ParseError;
 use nom::{
     branch::alt,
-    bytes::streaming::tag,
+    bytes::complete::tag,
     character::complete::{digit1, space1},
     character::{complete::char, complete::space0},
-    combinator::eof,
+    combinator::{eof, map_res},
     error::ErrorKind,
-    multi::{many1, separated_list1},
+    multi::separated_list1,
     sequence::{delimited, preceded, terminated, tuple},
     Finish, IResult, Parser,
 };
-use std::{fs::File, io::Read, path::Path, collections::VecDeque};
+use std::collections::VecDeque;
 
 mod chain;
 
@@ -23,29 +24,21 @@ fn eol(i: &str) -> IResult<&str, &str> {
     alt((eof, tag("\r\n"), tag("\n")))(i)
 }
 
+fn number(i: &str) -> IResult<&str, usize> {
+    map_res(digit1, str::parse::<usize>)(i)
+}
+
 fn prop_starting(i: &str) -> IResult<&str, VecDeque<usize>> {
     let (rest, items) = preceded(
         tag("Starting items: "),
-        separated_list1(
-            char(','),
-            delimited(space0, digit1, space0)
-                .map(|v: &str| v.parse::<usize>().expect("digits to be parsable as usize")),
-        ),
+        separated_list1(char(','), delimited(space0, number, space0)),
     )(i)?;
     Ok((rest, items.into()))
 }
 
 fn parse_formula(i: &str) -> IResult<&str, Operation> {
     fn parse_rvalue(i: &str) -> IResult<&str, RValue> {
-        alt((
-            tag("old").map(|_| RValue::Old),
-            digit1.map(|v: &str| {
-                RValue::Literal(
-                    v.parse::<usize>()
-                        .expect("to be able to parse matched digits"),
-                )
-            }),
-        ))(i.trim())
+        alt((tag("old").map(|_| RValue::Old), number.map(RValue::Literal)))(i.trim())
     }
 
     fn parse_operator(i: &str) -> IResult<&str, Operator> {
@@ -79,23 +72,18 @@ fn prop_operation(i: &str) -> IResult<&str, Operation> {
 
 fn prop_test(indent: &str) -> impl Fn(&str) -> IResult<&str, Test> + '_ {
     move |i: &str| {
-        let (rest, divider) = preceded(
-            tag("Test: divisible by "),
-            digit1.map(|v: &str| v.parse::<usize>().expect("digits to be parsable as usize")),
-        )(i)?;
+        let (rest, divider) = preceded(tag("Test: divisible by "), number)(i)?;
         let (rest, if_true) = eol
             .chain(tag(indent))
             .chain(tag("  "))
             .chain(tag("If true: throw to monkey "))
-            .chain(digit1)
-            .map(|v: &str| v.parse::<usize>().expect("digits to be parsable as usize"))
+            .chain(number)
             .parse(rest)?;
         let (rest, if_false) = eol
             .chain(tag(indent))
             .chain(tag("  "))
             .chain(tag("If false: throw to monkey "))
-            .chain(digit1)
-            .map(|v: &str| v.parse::<usize>().expect("digits to be parsable as usize"))
+            .chain(number)
             .parse(rest)?;
         let test = Test {
             divider,
@@ -107,11 +95,7 @@ fn prop_test(indent: &str) -> impl Fn(&str) -> IResult<&str, Test> + '_ {
 }
 
 fn parse_monkey(i: &str) -> IResult<&str, (usize, Monkey)> {
-    let (i, monkey_i) = delimited(
-        tag("Monkey "),
-        digit1.map(|v: &str| v.parse::<usize>().expect("digits to be parsable as usize")),
-        tag(":"),
-    )(i)?;
+    let (i, monkey_i) = delimited(tag("Monkey "), number, tag(":"))(i)?;
     let (i, _char) = eol(i)?;
     let (i, indent) = space1(i)?;
     let (i, items) = terminated(prop_starting, eol).parse(i)?;
@@ -125,26 +109,72 @@ fn parse_monkey(i: &str) -> IResult<&str, (usize, Monkey)> {
     Ok((i, (monkey_i, monkey)))
 }
 
-pub fn parse<P: AsRef<Path>>(file: P) -> Vec<Monkey> {
-    let mut contents = String::new();
-    File::open(file)
-        .expect("input file to exist and be readable")
-        .read_to_string(&mut contents)
-        .expect("to be able to read input file into memory");
-
-    let (_, parse_output) =
-        separated_list1(many1(space0.chain(eol)), parse_monkey)(contents.trim())
-            .finish()
-            .expect("to correctly parse the input file");
-    let (monkey_indices, monkeys): (Vec<usize>, Vec<Monkey>) = parse_output.into_iter().unzip();
-
-    for (i, parsed_i) in monkey_indices.into_iter().enumerate() {
-        if i != parsed_i {
-            panic!(
-                "Correctly parsed monkeys, but the parsed indices do not match expected indices!"
-            );
+pub fn parse(contents: &str) -> Result<Vec<Monkey>, ParseError> {
+    // Every slice nom hands back borrows from `contents`, so their offsets tell where they are.
+    let error_at = |message: &str, at: &str| {
+        let offset = at.as_ptr() as usize - contents.as_ptr() as usize;
+        ParseError::at_offset(message, contents, offset)
+    };
+
+    let mut monkeys = vec![];
+    // Where each monkey throws to, checked once we know how many monkeys there are.
+    let mut targets = vec![];
+    let mut rest = contents.trim_start();
+    while !rest.is_empty() {
+        let (after, (index, monkey)) = parse_monkey(rest).finish().map_err(|e| {
+            let message = match e.code {
+                ErrorKind::MapRes => "number is too large",
+                ErrorKind::Digit => "expected a number",
+                ErrorKind::Space => "expected the monkey's properties to be indented",
+                _ => "unexpected text in the monkey's description",
+            };
+            error_at(message, e.input)
+        })?;
+        if index != monkeys.len() {
+            let at = &rest["Monkey ".len()..];
+            return Err(error_at(&format!("expected monkey {}", monkeys.len()), at));
         }
+
+        // The number following the `label`, which the monkey's description is known to have.
+        let description = &rest[..rest.len() - after.len()];
+        let number_after = |label: &str| {
+            let start = description.find(label).expect("the label to have parsed") + label.len();
+            &description[start..]
+        };
+        if monkey.test.divider == 0 {
+            let at = number_after("divisible by ");
+            return Err(error_at("cannot test for divisibility by zero", at));
+        }
+        for (target, label) in [
+            (monkey.test.if_true, "If true: throw to monkey "),
+            (monkey.test.if_false, "If false: throw to monkey "),
+        ] {
+            if target == index {
+                let at = number_after(label);
+                return Err(error_at("a monkey cannot throw to itself", at));
+            }
+            targets.push((target, number_after(label)));
+        }
+        monkeys.push(monkey);
+
+        let (after, _) =
+            space0
+                .chain(eol)
+                .parse(after)
+                .finish()
+                .map_err(|e: nom::error::Error<&str>| {
+                    error_at("expected the end of the line", e.input)
+                })?;
+        rest = after.trim_start();
     }
 
-    monkeys
+    if monkeys.is_empty() {
+        return Err(ParseError::at_end("expected at least one monkey", contents));
+    }
+    for (target, at) in targets {
+        if target >= monkeys.len() {
+            return Err(error_at(&format!("there is no monkey {}", target), at));
+        }
+    }
+    Ok(monkeys)
 }
@@ -1,47 +1,28 @@
-use std::fmt::Debug;
-
 /// Partially sort the given slice from largest to smallest item, ensuring sorted order only for
-/// elements of index ≤ k.
+/// the first k elements.
 pub fn partial_sort<T: Ord>(slice: &mut [T], k: usize) {
-    partial_sort_recursive(slice, k, 0)
-}
-
-fn partial_sort_recursive<T: Ord>(slice: &mut [T], k: usize, i: usize) {
     // Quick sort, but discarding ordering to the right of pivots @ index k or greater.
     // See https://en.m.wikipedia.org/wiki/Partial_sorting#Specialised_sorting_algorithms
-    if slice.len() < 2 {
+    if slice.len() < 2 || k == 0 {
         return;
     }
-    if slice.len() == 2 {
-        if slice[0] < slice[1] {
-            slice.swap(0, 1);
+    // Use the middle element as the pivot, moving it out of the way to the end, and everything
+    // larger than it to the front.
+    let last = slice.len() - 1;
+    slice.swap(slice.len() / 2, last);
+    let mut pivot = 0;
+    for i in 0..last {
+        if slice[i] > slice[last] {
+            slice.swap(i, pivot);
+            pivot += 1;
         }
-        return;
     }
-    let mut left = 0;
-    let mut right = slice.len() - 1;
-    let partition = slice.len() / 2;
-    let mut pivot = slice.len() / 2;
-    loop {
-        while left < right && slice[left] > slice[pivot] {
-            left += 1;
-        }
-        while left < right && slice[right] < slice[pivot] {
-            right -= 1;
-        }
-        if left >= right {
-            break;
-        }
-        slice.swap(left, right);
-        if left == pivot {
-            pivot = right;
-        } else if right == pivot {
-            pivot = left;
-        }
-    }
-    partial_sort_recursive(&mut slice[0..=pivot], k, i);
-    if i + pivot < k {
-        partial_sort_recursive(&mut slice[(pivot + 1)..], k, i + partition + 1);
+    slice.swap(pivot, last);
+
+    // The pivot is now in its place, with everything larger to its left.
+    partial_sort(&mut slice[..pivot], k);
+    if pivot + 1 < k {
+        partial_sort(&mut slice[pivot + 1..], k - pivot - 1);
     }
 }
 
@@ -49,5 +30,27 @@ fn partial_sort_recursive<T: Ord>(slice: &mut [T], k: usize, i: usize) {
 fn partial_sort_test() {
     let mut x = [5, 1, 2, 9, 7, 6, 3, 1];
     partial_sort(&mut x, 3);
-    println!("{:?}", x);
-}
\ No newline at end of file
+    assert_eq!(&x[..3], &[9, 7, 6]);
+}
+
+#[test]
+fn matches_sorting() {
+    use aoc_check::{ints, vecs, Check};
+
+    let cases = (vecs(ints(0, 20), 0), ints(0, 10));
+    Check::new().differential(
+        &cases,
+        |(values, k)| {
+            let mut values = values.clone();
+            partial_sort(&mut values, *k as usize);
+            values.truncate(*k as usize);
+            values
+        },
+        |(values, k)| {
+            let mut values = values.clone();
+            values.sort_unstable_by(|a, b| b.cmp(a));
+            values.truncate(*k as usize);
+            values
+        },
+    );
+}
@@ -0,0 +1,35 @@
+use aoc_core::Rng;
+
+/// A random `side` by `side` height map (at least 28 squares across), where the goal can be
+/// reached from the start.
+pub fn generate(rng: &mut Rng, side: usize) -> String {
+    let side = side.max(28);
+    let mut map: Vec<Vec<u8>> = (0..side)
+        .map(|_| (0..side).map(|_| b'a' + rng.below(26) as u8).collect())
+        .collect();
+
+    // Walk right and down from the start, near the top-left corner, to the goal, near the
+    // bottom-right corner, climbing steadily so that every step can be taken.
+    let corner = |rng: &mut Rng, from| (from + rng.index(side / 4), from + rng.index(side / 4));
+    let (start, goal) = (corner(rng, 0), corner(rng, side - side / 4));
+    let length = goal.0 - start.0 + goal.1 - start.1;
+    let (mut x, mut y) = start;
+    for step in 0..=length {
+        map[y][x] = b'a' + (step * 25 / length) as u8;
+        let right = x < goal.0 && (y == goal.1 || rng.one_in(2));
+        if right {
+            x += 1;
+        } else if y < goal.1 {
+            y += 1;
+        }
+    }
+    map[start.1][start.0] = b'S';
+    map[goal.1][goal.0] = b'E';
+
+    let mut input = String::with_capacity((side + 1) * side);
+    for row in map {
+        input.extend(row.into_iter().map(char::from));
+        input.push('\n');
+    }
+    input
+}
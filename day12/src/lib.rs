@@ -0,0 +1,195 @@
+mod generate;
+
+pub use generate::generate;
+
+use aoc_core::{ParseError, Result};
+use aoc_grid::{Grid, Point};
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::{self, Read},
+};
+
+#[derive(Debug)]
+struct HeightMap {
+    inner: Grid<u8>,
+    start: Point,
+    goal: Point,
+}
+
+impl HeightMap {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut start = None;
+        let mut goal = None;
+        let inner = Grid::parse(input, |point, char_| match char_ {
+            'S' => {
+                if start.is_some() {
+                    return Err("found a second start".to_string());
+                }
+                start = Some(point);
+                Ok(b'a') // Essentially a dummy value
+            }
+            'E' => {
+                if goal.is_some() {
+                    return Err("found a second goal".to_string());
+                }
+                goal = Some(point);
+                Ok(b'z') // The goal has the highest elevation
+            }
+            c @ 'a'..='z' => Ok(c as u8),
+            c => Err(format!(
+                "expected an elevation (a-z, S or E), found {:?}",
+                c
+            )),
+        })?;
+        let start = start.ok_or_else(|| ParseError::at_end("no start (S) found", input))?;
+        let goal = goal.ok_or_else(|| ParseError::at_end("no goal (E) found", input))?;
+
+        Ok(HeightMap { inner, start, goal })
+    }
+
+    fn neighbours(&self, to: Point) -> impl Iterator<Item = Point> + '_ {
+        let center_height = self.inner[to];
+        self.inner.neighbours4(to).filter(move |&x| {
+            let other_height = self.inner[x];
+            other_height < center_height || other_height - center_height <= 1
+        })
+    }
+
+    fn navigate(&self) -> Option<usize> {
+        // A Star; how fitting!
+
+        let mut cost = HashMap::<Point, usize>::new();
+        let mut heuristic = BTreeSet::<(usize, Point)>::new();
+
+        cost.insert(self.start, 0);
+        heuristic.insert((self.start.manhattan(self.goal), self.start));
+
+        loop {
+            let (_heuristic_score, current) = heuristic.pop_first()?;
+            if current == self.goal {
+                // The cost of a square is the length of the shortest path found to it.
+                return Some(cost[&current]);
+            }
+
+            let current_score = *cost.get(&current).unwrap();
+
+            for neighbour in self.neighbours(current) {
+                let tentative_cost = current_score + 1;
+                let neighbour_cost = *cost.get(&neighbour).unwrap_or(&usize::MAX);
+                if tentative_cost < neighbour_cost {
+                    let old_neighbour_heuristic =
+                        neighbour_cost.saturating_add(neighbour.manhattan(self.goal));
+                    heuristic.remove(&(old_neighbour_heuristic, neighbour));
+                    let neighbour_heuristic = tentative_cost + neighbour.manhattan(self.goal);
+                    cost.insert(neighbour, tentative_cost);
+                    heuristic.insert((neighbour_heuristic, neighbour));
+                }
+            }
+        }
+    }
+
+    /// An error pointing at the goal, for when it can't be reached from any of the `starts`.
+    fn unreachable(&self, input: &str, starts: &str) -> ParseError {
+        let (x, y) = (self.goal.x as usize, self.goal.y as usize);
+        let line = input.lines().nth(y).unwrap_or_default();
+        ParseError::new(format!("no path from {} to E", starts), y + 1, x + 1, line)
+    }
+}
+
+#[test]
+fn parsing() {
+    println!(
+        "{:?}",
+        HeightMap::parse(&std::fs::read_to_string("input").unwrap())
+    )
+}
+
+#[test]
+fn neighbours() {
+    let map = HeightMap::parse(&std::fs::read_to_string("input").unwrap()).unwrap();
+    for neighbour in map.neighbours(Point::new(3, 1)) {
+        println!("{:?}", neighbour);
+    }
+}
+
+#[test]
+fn exampleinput() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part1(input).unwrap(), 31);
+}
+
+#[test]
+fn malformed() {
+    let error = solve_part1("Sab\nc7d\nfgE\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (2, 2));
+
+    let error = solve_part1("Sab\ncd\nfgE\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (2, 3));
+
+    let error = solve_part1("Sab\ncde\nfgh\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (3, 4));
+}
+
+#[test]
+fn exampleinput2() {
+    // The goal is walled off by squares that are too low to climb onto it from.
+    let input = std::fs::read_to_string("exampleinput2").unwrap();
+    let error = solve_part1(input.as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!(error.message(), "no path from S to E");
+    assert_eq!((error.line(), error.column()), (3, 5));
+    let error = solve_part2(input.as_bytes()).unwrap_err();
+    assert_eq!(
+        error.as_parse().unwrap().message(),
+        "no path from any a to E"
+    );
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub fn parse(input: impl Read) -> Result<impl Sized> {
+    Ok(HeightMap::parse(&io::read_to_string(input)?)?)
+}
+
+/// The fewest steps needed to reach the goal from the marked start.
+pub fn solve_part1(input: impl Read) -> Result<usize> {
+    let input = io::read_to_string(input)?;
+    let map = HeightMap::parse(&input)?;
+    Ok(map.navigate().ok_or_else(|| map.unreachable(&input, "S"))?)
+}
+
+/// The fewest steps needed to reach the goal from any square of elevation `a`.
+pub fn solve_part2(input: impl Read) -> Result<usize> {
+    let input = io::read_to_string(input)?;
+    let map = HeightMap::parse(&input)?;
+    let possible_starts: Vec<Point> = map
+        .inner
+        .iter()
+        .filter(|(_, &x)| x == b'a')
+        .map(|(point, _)| point)
+        .collect();
+
+    let mut map = map;
+    let mut best_start = None;
+    for start in possible_starts {
+        map.start = start;
+        if let Some(steps) = map.navigate() {
+            best_start = Some(match best_start {
+                Some((best_steps, best_start)) => {
+                    if steps < best_steps {
+                        (steps, start)
+                    } else {
+                        (best_steps, best_start)
+                    }
+                }
+                None => (steps, start),
+            });
+        }
+    }
+    match best_start {
+        Some((steps, _)) => Ok(steps),
+        None => Err(map.unreachable(&input, "any a").into()),
+    }
+}
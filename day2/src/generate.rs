@@ -0,0 +1,13 @@
+use aoc_core::Rng;
+
+/// A random strategy guide with `rounds` rounds.
+pub fn generate(rng: &mut Rng, rounds: usize) -> String {
+    let mut input = String::with_capacity(rounds * 4);
+    for _ in 0..rounds {
+        input.push(*rng.choose(&['A', 'B', 'C']));
+        input.push(' ');
+        input.push(*rng.choose(&['X', 'Y', 'Z']));
+        input.push('\n');
+    }
+    input
+}
@@ -0,0 +1,136 @@
+use crate::{Column, Outcome, Rules};
+
+/// What a letter of the second column is taken to stand for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Meaning {
+    Play(usize),
+    Outcome(Outcome),
+}
+
+/// A way of reading the second column, and what the guide scores read that way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    pub column: Column,
+    pub letters: Vec<(String, Meaning)>,
+    pub score: u32,
+}
+
+impl Mapping {
+    /// The letters and what they stand for, e.g. `X=rock Y=paper Z=scissors`.
+    pub fn describe(&self, rules: &Rules) -> String {
+        let letters: Vec<_> = (self.letters.iter())
+            .map(|(letter, meaning)| match meaning {
+                Meaning::Play(throw) => format!("{}={}", letter, rules.name(*throw)),
+                Meaning::Outcome(outcome) => format!("{}={}", letter, outcome.name()),
+            })
+            .collect();
+        letters.join(" ")
+    }
+}
+
+/// Every way of reading the second column: each letter standing for a different throw, and each
+/// for a different outcome (or, with more letters than those, for any), scored over the guide,
+/// given as pairs of the opponent's throw and the letter (numbered as in `Rules::second_letters`).
+pub(crate) fn mappings(rules: &Rules, guide: &[(usize, usize)]) -> Vec<Mapping> {
+    let letters = rules.second_letters();
+    // Only how often each pair turns up matters to the scores.
+    let mut counts = vec![vec![0; letters.len()]; rules.throws()];
+    for &(opponent, letter) in guide {
+        counts[opponent][letter] += 1;
+    }
+    let score = |throw: &dyn Fn(usize, usize) -> usize| -> u32 {
+        let mut score = 0;
+        for (opponent, counts) in counts.iter().enumerate() {
+            for (letter, &count) in counts.iter().enumerate() {
+                score += count * rules.score(throw(opponent, letter), opponent);
+            }
+        }
+        score
+    };
+    let mapping = |column, meanings: Vec<Meaning>, score| Mapping {
+        column,
+        letters: (letters.iter().map(|(letter, _)| letter.clone()))
+            .zip(meanings)
+            .collect(),
+        score,
+    };
+
+    let mut mappings = vec![];
+    for throws in readings(rules.throws(), letters.len()) {
+        let score = score(&|_, letter| throws[letter]);
+        let meanings = throws.into_iter().map(Meaning::Play).collect();
+        mappings.push(mapping(Column::Play, meanings, score));
+    }
+    for outcomes in readings(Outcome::ALL.len(), letters.len()) {
+        let outcomes: Vec<_> = outcomes.into_iter().map(|i| Outcome::ALL[i]).collect();
+        let score = score(&|opponent, letter| rules.throw_for(opponent, outcomes[letter]));
+        let meanings = outcomes.into_iter().map(Meaning::Outcome).collect();
+        mappings.push(mapping(Column::Outcome, meanings, score));
+    }
+    mappings
+}
+
+/// Every way of picking `k` numbers below `n`, in order, in lexicographic order: all different
+/// if there are enough to go round, and any at all (repeats included) if not.
+fn readings(n: usize, k: usize) -> Vec<Vec<usize>> {
+    let mut readings = vec![vec![]];
+    for _ in 0..k {
+        readings = (readings.into_iter())
+            .flat_map(|reading: Vec<usize>| {
+                (0..n)
+                    .filter(|i| k > n || !reading.contains(i))
+                    .map(|i| [&reading[..], &[i]].concat())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+    readings
+}
+
+#[test]
+fn exampleinput() {
+    let rules = Rules::default();
+    let input = std::fs::read("exampleinput").unwrap();
+    let mappings = crate::mappings(&input[..], &rules).unwrap();
+    assert_eq!(mappings.len(), 6 + 6);
+    let described: Vec<_> = (mappings.iter())
+        .map(|mapping| (mapping.describe(&rules), mapping.score))
+        .collect();
+    assert!(described.contains(&("X=rock Y=paper Z=scissors".to_string(), 15)));
+    assert!(described.contains(&("X=lose Y=draw Z=win".to_string(), 12)));
+
+    // Each scores what the same guide does under rules spelling the mapping out.
+    let config = include_str!("../rules/rock-paper-scissors");
+    let kept: Vec<_> = (config.lines())
+        .filter(|line| !line.starts_with("play") && !line.starts_with("outcome"))
+        .collect();
+    for mapping in &mappings {
+        let mut config = kept.join("\n");
+        for (letter, meaning) in &mapping.letters {
+            config += &match meaning {
+                Meaning::Play(throw) => format!("\nplay {} {}", letter, rules.name(*throw)),
+                Meaning::Outcome(outcome) => format!("\noutcome {} {}", letter, outcome.name()),
+            };
+        }
+        let spelled_out = Rules::parse(&config).unwrap();
+        let score = crate::score(&input[..], &spelled_out, mapping.column).unwrap();
+        assert_eq!(score, mapping.score, "{}", mapping.describe(&rules));
+    }
+}
+
+#[test]
+fn more_letters_than_outcomes() {
+    let rules = Rules::parse(include_str!("../rules/rock-paper-scissors-lizard-spock")).unwrap();
+    let input = "A X\nD V\nE W\n";
+    let mappings = crate::mappings(input.as_bytes(), &rules).unwrap();
+    let (play, outcome): (Vec<_>, Vec<_>) =
+        (mappings.iter()).partition(|mapping| mapping.column == Column::Play);
+    assert_eq!((play.len(), outcome.len()), (120, 3 * 3 * 3 * 3 * 3));
+    let described: Vec<_> = (mappings.iter())
+        .map(|mapping| (mapping.describe(&rules), mapping.score))
+        .collect();
+    // Winning every round, with the best-scoring throw that does: paper against rock, scissors
+    // against lizard and lizard against spock.
+    let all_wins = "X=win Y=win Z=win V=win W=win".to_string();
+    assert!(described.contains(&(all_wins, 3 + 6 + 5 + 6 + 4 + 6)));
+}
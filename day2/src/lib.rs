@@ -0,0 +1,202 @@
+mod generate;
+mod infer;
+mod rules;
+mod strategy;
+mod tournament;
+mod transcript;
+
+pub use generate::generate;
+pub use infer::{Mapping, Meaning};
+pub use rules::{Outcome, Rules};
+pub use strategy::{Constraints, Strategy};
+pub use tournament::{Match, Standing, Tournament};
+pub use transcript::{write_csv, Round};
+
+use aoc_core::{ParseError, Result};
+use rules::{either, letter, Letters};
+use std::io::{BufRead, BufReader, Read};
+
+/// What the second column of the strategy guide means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// The throw to play.
+    Play,
+    /// The outcome to aim for.
+    Outcome,
+}
+
+/// The message for a column that doesn't start with one of the letters.
+fn expected<T>(letters: &Letters<T>, found: Option<char>) -> String {
+    let letters: Vec<_> = letters.iter().map(|(letter, _)| letter.as_str()).collect();
+    match found {
+        Some(c) => format!("expected {}, found {:?}", either(&letters), c),
+        None => format!("expected {}", either(&letters)),
+    }
+}
+
+/// The opponent's throw at the start of a line of the guide, and the length of its letter.
+fn opponent(rules: &Rules, line: &str, line_no: usize) -> Result<(usize, usize), ParseError> {
+    letter(rules.opponent_letters(), line)
+        .map(|(length, opponent)| (opponent, length))
+        .ok_or_else(|| {
+            let message = expected(rules.opponent_letters(), line.chars().next());
+            ParseError::new(message, line_no + 1, 1, line)
+        })
+}
+
+/// Reads the opponent's throws from the strategy guide's first column, ignoring the second.
+fn opponents(input: impl Read, rules: &Rules) -> Result<Vec<usize>> {
+    let mut opponents = vec![];
+    for (line_no, line) in BufReader::new(input).lines().enumerate() {
+        opponents.push(opponent(rules, &line?, line_no)?.0);
+    }
+    Ok(opponents)
+}
+
+/// Reads the strategy guide, as pairs of the opponent's throw (numbered as in the rules) and what
+/// the second column's letter stands for in `second`.
+fn guide<T: Copy>(input: impl Read, rules: &Rules, second: &Letters<T>) -> Result<Vec<(usize, T)>> {
+    let mut guide = vec![];
+    for (line_no, line) in BufReader::new(input).lines().enumerate() {
+        let line = line?;
+        let error = |message: &str, column| ParseError::new(message, line_no + 1, column, &line);
+        let (opponent, length) = opponent(rules, &line, line_no)?;
+        let mut chars = line.chars().skip(length);
+        if chars.next() != Some(' ') {
+            return Err(error("expected a space after the first column", length + 1).into());
+        }
+
+        let rest: String = chars.collect();
+        let Some((_, meaning)) = letter(second, &rest) else {
+            let message = expected(second, rest.chars().next());
+            return Err(error(&message, length + 2).into());
+        };
+        guide.push((opponent, meaning));
+    }
+    Ok(guide)
+}
+
+/// Reads the strategy guide, as pairs of the opponent's throw and the throw to play (numbered as
+/// in the rules), taking the second column as `column` says.
+fn rounds(input: impl Read, rules: &Rules, column: Column) -> Result<Vec<(usize, usize)>> {
+    Ok(match column {
+        Column::Play => guide(input, rules, rules.play_letters())?,
+        Column::Outcome => (guide(input, rules, rules.outcome_letters())?.into_iter())
+            .map(|(opponent, outcome)| (opponent, rules.throw_for(opponent, outcome)))
+            .collect(),
+    })
+}
+
+/// The score for playing each round's throw against the opponent's.
+fn as_play_score(rules: &Rules, rounds: &[(usize, usize)]) -> u32 {
+    (rounds.iter())
+        .map(|&(opponent, throw)| rules.score(throw, opponent))
+        .sum()
+}
+
+/// The highest-scoring throws to play against the opponent's throws in the strategy guide's first
+/// column (the second is ignored), within the constraints; see `Strategy::best`.
+pub fn best_strategy(
+    input: impl Read,
+    rules: &Rules,
+    constraints: &Constraints,
+) -> Result<Option<Strategy>> {
+    Ok(Strategy::best(
+        rules,
+        &opponents(input, rules)?,
+        constraints,
+    ))
+}
+
+/// Every way of reading the strategy guide's second column, as throws to play and as outcomes to
+/// aim for, and what the guide scores read that way, for guides whose meaning isn't known.
+pub fn mappings(input: impl Read, rules: &Rules) -> Result<Vec<Mapping>> {
+    let guide = guide(input, rules, &rules.second_letters())?;
+    Ok(infer::mappings(rules, &guide))
+}
+
+/// Plays the strategy guide under the rules, taking the second column as `column` says, giving
+/// every round's throws, outcome and points.
+pub fn transcript(input: impl Read, rules: &Rules, column: Column) -> Result<Vec<Round>> {
+    Ok(transcript::transcript(
+        rules,
+        &rounds(input, rules, column)?,
+    ))
+}
+
+/// Scores the strategy guide under the rules, taking the second column as `column` says.
+pub fn score(input: impl Read, rules: &Rules, column: Column) -> Result<u32> {
+    Ok(as_play_score(rules, &rounds(input, rules, column)?))
+}
+
+#[test]
+fn exampleinput() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part1(input).unwrap(), 15);
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part2(input).unwrap(), 12);
+}
+
+#[test]
+fn malformed() {
+    let error = solve_part1("A Y\nB-X\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (2, 2));
+}
+
+#[test]
+fn other_rules() {
+    let rules = Rules::parse(include_str!("../rules/rock-paper-scissors-lizard-spock")).unwrap();
+    let score = |guide: &str, column| score(guide.as_bytes(), &rules, column);
+    // Spock vaporizes rock, lizard draws, and spock smashes scissors.
+    assert_eq!(score("A W\nD V\nE Z\n", Column::Play).unwrap(), 8 + 7 + 5);
+    // Losing to rock with scissors rather than lizard, and beating spock with lizard rather than
+    // paper, as they score more.
+    assert_eq!(score("A X\nE Z\n", Column::Outcome).unwrap(), 5 + 10);
+    let error = score("A X\nA V\n", Column::Outcome).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (2, 3));
+    assert_eq!(error.message(), "expected X, Y or Z, found 'V'");
+}
+
+#[test]
+fn matches_arithmetic() {
+    use aoc_check::{ints, Check};
+    use aoc_core::Rng;
+
+    // The default rules against the arithmetic mod 3 that this day used to be solved with.
+    Check::new().differential(
+        &ints(0, u64::MAX),
+        |&seed| {
+            let input = generate(&mut Rng::new(seed), 30);
+            let part1 = solve_part1(input.as_bytes()).unwrap();
+            (part1, solve_part2(input.as_bytes()).unwrap())
+        },
+        |&seed| {
+            let input = generate(&mut Rng::new(seed), 30);
+            let (mut part1, mut part2) = (0u32, 0u32);
+            for line in input.lines() {
+                let opponent = (line.as_bytes()[0] - b'A') as u32;
+                let second = (line.as_bytes()[2] - b'X') as u32;
+                part1 += (second + 4 - opponent) % 3 * 3 + second + 1;
+                part2 += second * 3 + (opponent + 2 + second) % 3 + 1;
+            }
+            (part1, part2)
+        },
+    );
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub fn parse(input: impl Read) -> Result<impl Sized> {
+    rounds(input, &Rules::default(), Column::Play)
+}
+
+/// The total score if the second column is the throw to play.
+pub fn solve_part1(input: impl Read) -> Result<u32> {
+    score(input, &Rules::default(), Column::Play)
+}
+
+/// The total score if the second column is the desired outcome.
+pub fn solve_part2(input: impl Read) -> Result<u32> {
+    score(input, &Rules::default(), Column::Outcome)
+}
@@ -1,59 +1,156 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-fn main() {
-    let readin = BufReader::new(File::open("input").expect("the file to exist and be readable"));
-
-    let mut as_play_score = 0;
-    let mut as_outcome_score = 0;
-
-    for line in readin.lines() {
-        let line = line.expect("every line in the file to be readable");
-        let mut chars = line.chars();
-        let opponent: u8 = match chars.next().expect("the line to have a first character") {
-            'A' => 0,
-            'B' => 1,
-            'C' => 2,
-            c => {
-                panic!("Unexpected character {} found in the first column!", c);
-            }
+use aoc_core::{InFile, Input, Report};
+use day2::{Column, Constraints, Mapping, Rules, Tournament};
+use std::{env, fs, io};
+
+/// The value given with the `option`, if any.
+fn option(option: &str) -> Option<String> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == option {
+            return Some(args.next().unwrap_or_default());
+        }
+    }
+    None
+}
+
+/// The rules given with `--rules <file>`, or rock paper scissors.
+fn rules() -> aoc_core::Result<Rules> {
+    let Some(path) = option("--rules") else {
+        return Ok(Rules::default());
+    };
+    let config = Input::read(path)?;
+    Ok(Rules::parse(config.contents()).map_err(|e| e.in_file(config.name()))?)
+}
+
+/// The meaning of the second column given with `--column <play|outcome>`, or a throw to play.
+fn column() -> io::Result<Column> {
+    match option("--column").as_deref() {
+        None | Some("play") => Ok(Column::Play),
+        Some("outcome") => Ok(Column::Outcome),
+        Some(other) => {
+            let message = format!("expected play or outcome, found {:?}", other);
+            Err(io::Error::new(io::ErrorKind::InvalidInput, message))
+        }
+    }
+}
+
+fn main() -> aoc_core::Result<()> {
+    let rules = rules()?;
+    let column = column()?;
+
+    if let Some(paths) = option("--tournament") {
+        let mut names = vec![];
+        let mut players = vec![];
+        for path in paths.split(',').filter(|path| !path.is_empty()) {
+            let guide = Input::read(path)?;
+            let rounds =
+                day2::transcript(guide.as_bytes(), &rules, column).in_file(guide.name())?;
+            players.push(rounds.iter().map(|round| round.throw).collect());
+            names.push(guide.name().to_string());
+        }
+        let tournament = Tournament::round_robin(&rules, &players);
+        for m in &tournament.matches {
+            let (a, b) = m.players;
+            println!("{} {} - {} {}", names[a], m.scores.0, m.scores.1, names[b]);
+        }
+        println!();
+        let width = names.iter().map(String::len).max().unwrap_or(0).max(5);
+        println!("Rank  {:<width$}  W  D  L  Points     Score", "Guide");
+        for (rank, standing) in tournament.standings.iter().enumerate() {
+            println!(
+                "{:>4}  {:<width$}  {}  {}  {}  {:>6}  {:>8}",
+                rank + 1,
+                names[standing.player],
+                standing.won,
+                standing.drawn,
+                standing.lost,
+                standing.points(),
+                standing.score
+            );
+        }
+        return Ok(());
+    }
+
+    let input = Input::from_args(concat!(env!("CARGO_MANIFEST_DIR"), "/input"))?;
+
+    if let Some(path) = option("--transcript") {
+        let rounds = day2::transcript(input.as_bytes(), &rules, column).in_file(input.name())?;
+        match path.as_str() {
+            "-" => day2::write_csv(&rules, &rounds, io::stdout().lock())?,
+            _ => day2::write_csv(&rules, &rounds, io::BufWriter::new(fs::File::create(path)?))?,
+        }
+        return Ok(());
+    }
+
+    if let Some(limits) = option("--best") {
+        let constraints: Constraints = limits.parse()?;
+        let best = day2::best_strategy(input.as_bytes(), &rules, &constraints);
+        let Some(best) = best.in_file(input.name())? else {
+            println!("No throws keep within {}", limits);
+            return Ok(());
         };
-        match chars.next() {
-            Some(' ') => {}
-            _ => {
-                panic!("Expected the line to have a space after the first column");
-            }
+        println!("Round  Opponent  Play      Outcome  Points");
+        for (round, &(opponent, throw)) in best.rounds.iter().enumerate() {
+            println!(
+                "{:>5}  {:<8}  {:<8}  {:<7}  {:>6}",
+                round + 1,
+                rules.name(opponent),
+                rules.name(throw),
+                rules.outcome(throw, opponent).name(),
+                rules.score(throw, opponent)
+            );
         }
+        println!("Score: {}", best.score);
+        return Ok(());
+    }
 
-        let second_col: u8 = match chars.next().expect("the line to have a third column") {
-            'X' => 0,
-            'Y' => 1,
-            'Z' => 2,
-            c => {
-                panic!("Unexpected character {} found in the third column!", c);
-            }
+    if let Some(expected) = option("--infer") {
+        let expected = match expected.as_str() {
+            "any" => None,
+            _ => Some(expected.parse::<u32>().map_err(|_| {
+                let message = format!("{:?} is not a total score", expected);
+                io::Error::new(io::ErrorKind::InvalidInput, message)
+            })?),
         };
-        
-        // The %s below represent a useful way to think of the problem (since it has a cyclic structure):
-        //      If R = 0, P = 1, S = 2, notice that (x+1)%3 gives the throw to which x is weak.
-        //      Likewise, (x+2)%3 gives the throw to which x is strong.
-
-        let if_as_played: u32 = if second_col == ((opponent + 1) % 3) {
-            6 /* win */
-        } else if opponent == ((second_col + 1) % 3) {
-            0 /* loss */
-        } else {
-            3 /* tie */
+        let mappings = day2::mappings(input.as_bytes(), &rules).in_file(input.name())?;
+        let width = (mappings.iter())
+            .map(|mapping| mapping.describe(&rules).len())
+            .max()
+            .unwrap_or(0);
+        let row = |mapping: &Mapping| {
+            let column = match mapping.column {
+                Column::Play => "play",
+                Column::Outcome => "outcome",
+            };
+            let letters = mapping.describe(&rules);
+            format!("{:<7}  {:<width$}  {:>6}", column, letters, mapping.score)
         };
-
-        let if_as_outcome = (opponent + 2 + second_col) % 3;
-
-        as_play_score += if_as_played + (second_col as u32 + 1);
-        as_outcome_score += second_col as u32 * 3 + (if_as_outcome as u32 + 1);
+        println!("{:<7}  {:<width$}  {:>6}", "Column", "Letters", "Score");
+        for mapping in &mappings {
+            println!("{}", row(mapping));
+        }
+        if let Some(expected) = expected {
+            let consistent: Vec<_> = (mappings.iter())
+                .filter(|mapping| mapping.score == expected)
+                .collect();
+            println!();
+            match consistent.len() {
+                0 => println!("No mapping scores {}", expected),
+                n => println!("{} of them score {}:", n, expected),
+            }
+            for mapping in consistent {
+                println!("{}", row(mapping));
+            }
+        }
+        return Ok(());
     }
 
-    println!(
-        "Part 1 answer: {}\nPart 2 answer: {}",
-        as_play_score, as_outcome_score
-    );
+    let report = Report::from_args(2)?;
+    report.part(1, "Part 1 answer: {}", || {
+        day2::score(input.as_bytes(), &rules, Column::Play).in_file(input.name())
+    })?;
+    report.part(2, "Part 2 answer: {}", || {
+        day2::score(input.as_bytes(), &rules, Column::Outcome).in_file(input.name())
+    })?;
+    Ok(())
 }
@@ -0,0 +1,446 @@
+use aoc_core::ParseError;
+
+/// How a round ends, for the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+    const NAMES: [(&'static str, Outcome); 3] = [
+        ("lose", Outcome::Lose),
+        ("draw", Outcome::Draw),
+        ("win", Outcome::Win),
+    ];
+
+    pub fn name(self) -> &'static str {
+        Outcome::NAMES[self as usize].0
+    }
+}
+
+/// The rules of a rock-paper-scissors-like game, and how a strategy guide encodes it: the
+/// throws and which beats which, what each throw and outcome scores, and the letters for the
+/// opponent's throws and for the second column (as a throw to play, and as an outcome to aim for).
+///
+/// Rules are read from a small config file (see `rules/` for examples), one directive per line,
+/// with `#` starting a comment:
+///
+/// ```text
+/// throws rock paper scissors   # each beats the (n - 1) / 2 listed before it, cyclically
+/// beats paper rock             # or, if there are any of these, exactly the pairs given
+/// score rock 1                 # for every throw, and for lose, draw and win
+/// opponent A rock
+/// play X rock
+/// outcome X lose
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rules {
+    names: Vec<String>,
+    scores: Vec<u32>,
+    /// `beats[a][b]` if throw `a` beats throw `b`.
+    beats: Vec<Vec<bool>>,
+    outcome_scores: [u32; 3],
+    opponent: Letters<usize>,
+    play: Letters<usize>,
+    outcome: Letters<Outcome>,
+}
+
+/// The letters (or longer codes) of a column, and what each stands for.
+pub(crate) type Letters<T> = Vec<(String, T)>;
+
+impl Default for Rules {
+    /// Rock paper scissors, as the puzzle plays it.
+    fn default() -> Self {
+        Rules::parse(include_str!("../rules/rock-paper-scissors"))
+            .expect("the default rules to be valid")
+    }
+}
+
+impl Rules {
+    pub fn parse(config: &str) -> Result<Rules, ParseError> {
+        let mut names: Vec<String> = vec![];
+        // Where the throws were listed, for errors about them.
+        let mut throws_at = None;
+        let mut scores: Vec<Option<u32>> = vec![];
+        let mut outcome_scores = [None; 3];
+        let mut beats: Vec<Vec<bool>> = vec![];
+        let mut spelled_out = false;
+        let (mut opponent, mut play, mut outcome) = (vec![], vec![], vec![]);
+
+        for (line_no, line) in config.lines().enumerate() {
+            let error = |message: &str, column| ParseError::new(message, line_no + 1, column, line);
+            let words = words(line.split('#').next().unwrap_or_default());
+            let Some(&(column, directive)) = words.first() else {
+                continue;
+            };
+            let throw = |(column, name): (usize, &str)| {
+                let found = names.iter().position(|known| known == name);
+                found.ok_or_else(|| {
+                    let message = match names.is_empty() {
+                        true => "expected the throws to be listed first".to_string(),
+                        false => format!("expected {}, found {:?}", either(&names), name),
+                    };
+                    error(&message, column)
+                })
+            };
+            let arguments = |count: usize, what: &str| match words.len() - 1 == count {
+                true => Ok(words[1..].to_vec()),
+                false => Err(error(
+                    &format!("expected {} after {:?}", what, directive),
+                    column,
+                )),
+            };
+
+            match directive {
+                "throws" => {
+                    if throws_at.is_some() {
+                        return Err(error("the throws are already listed", column));
+                    }
+                    for &(column, name) in &words[1..] {
+                        if names.iter().any(|known| known == name) {
+                            return Err(error(&format!("{:?} is listed twice", name), column));
+                        }
+                        if Outcome::NAMES.iter().any(|&(outcome, _)| outcome == name) {
+                            let message = format!("{:?} is an outcome, not a throw", name);
+                            return Err(error(&message, column));
+                        }
+                        names.push(name.to_string());
+                    }
+                    if names.is_empty() {
+                        return Err(error("expected the names of the throws", column));
+                    }
+                    throws_at = Some((line_no, line));
+                    scores = vec![None; names.len()];
+                    beats = vec![vec![false; names.len()]; names.len()];
+                }
+                "beats" => {
+                    let words = arguments(2, "two throws")?;
+                    let (winner, loser) = (throw(words[0])?, throw(words[1])?);
+                    if winner == loser {
+                        return Err(error("a throw can't beat itself", words[1].0));
+                    }
+                    if beats[winner][loser] || beats[loser][winner] {
+                        let message =
+                            format!("{} and {} already have a winner", words[0].1, words[1].1);
+                        return Err(error(&message, words[0].0));
+                    }
+                    beats[winner][loser] = true;
+                    spelled_out = true;
+                }
+                "score" => {
+                    let words = arguments(2, "a throw or outcome and its score")?;
+                    let (name_column, name) = words[0];
+                    let score = words[1].1.parse().map_err(|_| {
+                        error(
+                            &format!("expected a score, found {:?}", words[1].1),
+                            words[1].0,
+                        )
+                    })?;
+                    let slot = match Outcome::NAMES.iter().find(|&&(outcome, _)| outcome == name) {
+                        Some(&(_, outcome)) => &mut outcome_scores[outcome as usize],
+                        None => &mut scores[throw(words[0])?],
+                    };
+                    if slot.is_some() {
+                        return Err(error(&format!("{} is already scored", name), name_column));
+                    }
+                    *slot = Some(score);
+                }
+                "opponent" | "play" => {
+                    let words = arguments(2, "a letter and a throw")?;
+                    let letters = match directive {
+                        "opponent" => &mut opponent,
+                        _ => &mut play,
+                    };
+                    let throw = throw(words[1])?;
+                    add_letter(letters, words[0].1, throw).map_err(|m| error(&m, words[0].0))?;
+                }
+                "outcome" => {
+                    let words = arguments(2, "a letter and an outcome")?;
+                    let (column, name) = words[1];
+                    let Some(&(_, to_aim_for)) = Outcome::NAMES.iter().find(|&&(o, _)| o == name)
+                    else {
+                        let message = format!("expected lose, draw or win, found {:?}", name);
+                        return Err(error(&message, column));
+                    };
+                    add_letter(&mut outcome, words[0].1, to_aim_for)
+                        .map_err(|m| error(&m, words[0].0))?;
+                }
+                _ => {
+                    let message = format!(
+                        "expected throws, beats, score, opponent, play or outcome, found {:?}",
+                        directive
+                    );
+                    return Err(error(&message, column));
+                }
+            }
+        }
+
+        let missing = |message: &str| ParseError::at_end(message, config);
+        let Some((throws_line, throws_excerpt)) = throws_at else {
+            return Err(missing("expected the throws to be listed"));
+        };
+        if !spelled_out {
+            // Each throw beats the half of the others listed before it, wrapping around.
+            let n = names.len();
+            if n.is_multiple_of(2) {
+                let message = format!(
+                    "expected an odd number of throws to play them cyclically, found {} \
+                     (or say which beats which with `beats`)",
+                    n
+                );
+                return Err(ParseError::new(message, throws_line + 1, 1, throws_excerpt));
+            }
+            for (winner, beats) in beats.iter_mut().enumerate() {
+                for behind in 1..=(n - 1) / 2 {
+                    beats[(winner + n - behind) % n] = true;
+                }
+            }
+        }
+        for a in 0..names.len() {
+            for b in a + 1..names.len() {
+                if !beats[a][b] && !beats[b][a] {
+                    let message =
+                        format!("expected {} or {} to beat the other", names[a], names[b]);
+                    return Err(missing(&message));
+                }
+            }
+            // Otherwise some outcomes couldn't be aimed for against it.
+            if !(0..names.len()).any(|b| beats[b][a]) {
+                return Err(missing(&format!(
+                    "expected a throw that beats {}",
+                    names[a]
+                )));
+            }
+            if !beats[a].contains(&true) {
+                return Err(missing(&format!(
+                    "expected a throw that {} beats",
+                    names[a]
+                )));
+            }
+        }
+        let scores = (scores.iter().zip(&names))
+            .map(|(score, name)| {
+                score.ok_or_else(|| missing(&format!("expected a score for {}", name)))
+            })
+            .collect::<Result<_, _>>()?;
+        let mut scored = [0; 3];
+        for (name, outcome) in Outcome::NAMES {
+            scored[outcome as usize] = outcome_scores[outcome as usize]
+                .ok_or_else(|| missing(&format!("expected a score for {}", name)))?;
+        }
+        if opponent.is_empty() {
+            return Err(missing("expected letters for the opponent's throws"));
+        }
+        if play.is_empty() && outcome.is_empty() {
+            return Err(missing("expected letters for the second column"));
+        }
+
+        Ok(Rules {
+            names,
+            scores,
+            beats,
+            outcome_scores: scored,
+            opponent,
+            play,
+            outcome,
+        })
+    }
+
+    /// How many throws there are; throws are numbered in the order they're listed.
+    pub fn throws(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, throw: usize) -> &str {
+        &self.names[throw]
+    }
+
+    /// The outcome of playing `throw` against the opponent's.
+    pub fn outcome(&self, throw: usize, opponent: usize) -> Outcome {
+        if self.beats[throw][opponent] {
+            Outcome::Win
+        } else if self.beats[opponent][throw] {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// The score for playing `throw` against the opponent's: the throw's own, and the outcome's.
+    pub fn score(&self, throw: usize, opponent: usize) -> u32 {
+        self.scores[throw] + self.outcome_scores[self.outcome(throw, opponent) as usize]
+    }
+
+    /// The throw to play against the opponent's to get the outcome. If several would, it's the
+    /// one that scores the most (the first listed, of those that score the same).
+    pub fn throw_for(&self, opponent: usize, outcome: Outcome) -> usize {
+        (0..self.throws())
+            .filter(|&throw| self.outcome(throw, opponent) == outcome)
+            .rev()
+            .max_by_key(|&throw| self.scores[throw])
+            .expect("every outcome to be possible against every throw")
+    }
+
+    pub(crate) fn opponent_letters(&self) -> &Letters<usize> {
+        &self.opponent
+    }
+
+    pub(crate) fn play_letters(&self) -> &Letters<usize> {
+        &self.play
+    }
+
+    pub(crate) fn outcome_letters(&self) -> &Letters<Outcome> {
+        &self.outcome
+    }
+
+    /// Every letter of the second column, as a throw to play or as an outcome, each standing for
+    /// its own place in the list.
+    pub(crate) fn second_letters(&self) -> Letters<usize> {
+        let mut letters: Vec<&str> = self
+            .play
+            .iter()
+            .map(|(letter, _)| letter.as_str())
+            .collect();
+        for (letter, _) in &self.outcome {
+            if !letters.contains(&letter.as_str()) {
+                letters.push(letter);
+            }
+        }
+        (letters.into_iter().enumerate())
+            .map(|(i, letter)| (letter.to_string(), i))
+            .collect()
+    }
+}
+
+/// The line's words, with the (1-based) columns they start at.
+fn words(line: &str) -> Vec<(usize, &str)> {
+    let mut words = vec![];
+    let mut start = None;
+    for (column, (i, c)) in line.char_indices().chain([(line.len(), ' ')]).enumerate() {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some((column + 1, i)),
+            (Some((column, start_i)), true) => {
+                words.push((column, &line[start_i..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+fn add_letter<T>(letters: &mut Letters<T>, letter: &str, meaning: T) -> Result<(), String> {
+    if letters.iter().any(|(known, _)| known == letter) {
+        return Err(format!("{} already has a meaning here", letter));
+    }
+    letters.push((letter.to_string(), meaning));
+    Ok(())
+}
+
+/// The letter at the start of `text`, if any (the longest, if several are), with its length in
+/// characters.
+pub(crate) fn letter<T: Copy>(letters: &Letters<T>, text: &str) -> Option<(usize, T)> {
+    (letters.iter())
+        .filter(|(letter, _)| text.starts_with(letter.as_str()))
+        .max_by_key(|(letter, _)| letter.len())
+        .map(|(letter, meaning)| (letter.chars().count(), *meaning))
+}
+
+/// The names, listed for an error message: "A", "A or B", "A, B or C".
+pub(crate) fn either(names: &[impl AsRef<str>]) -> String {
+    match names {
+        [] => String::new(),
+        [only] => only.as_ref().to_string(),
+        [rest @ .., last] => {
+            let rest: Vec<_> = rest.iter().map(AsRef::as_ref).collect();
+            format!("{} or {}", rest.join(", "), last.as_ref())
+        }
+    }
+}
+
+#[test]
+fn cyclic() {
+    let rules = Rules::default();
+    let [rock, paper, scissors] = [0, 1, 2];
+    assert_eq!(rules.outcome(paper, rock), Outcome::Win);
+    assert_eq!(rules.outcome(rock, paper), Outcome::Lose);
+    assert_eq!(rules.outcome(rock, scissors), Outcome::Win);
+    assert_eq!(rules.score(scissors, scissors), 6);
+    assert_eq!(rules.throw_for(rock, Outcome::Lose), scissors);
+
+    let rules = Rules::parse(include_str!("../rules/rock-paper-scissors-lizard-spock")).unwrap();
+    let throw = |name| {
+        (0..rules.throws())
+            .find(|&t| rules.name(t) == name)
+            .unwrap()
+    };
+    for (winner, loser) in [
+        ("scissors", "paper"),
+        ("paper", "rock"),
+        ("rock", "lizard"),
+        ("lizard", "spock"),
+        ("spock", "scissors"),
+        ("scissors", "lizard"),
+        ("lizard", "paper"),
+        ("paper", "spock"),
+        ("spock", "rock"),
+        ("rock", "scissors"),
+    ] {
+        assert_eq!(rules.outcome(throw(winner), throw(loser)), Outcome::Win);
+        assert_eq!(rules.outcome(throw(loser), throw(winner)), Outcome::Lose);
+    }
+    // Both paper and spock beat rock; spock scores less.
+    assert_eq!(rules.throw_for(throw("rock"), Outcome::Win), throw("paper"));
+    assert_eq!(
+        rules.throw_for(throw("rock"), Outcome::Lose),
+        throw("scissors")
+    );
+}
+
+#[test]
+fn malformed_rules() {
+    let rules = include_str!("../rules/rock-paper-scissors");
+    let error = |config: &str| {
+        let error = Rules::parse(config).unwrap_err();
+        (error.line(), error.column(), error.message().to_string())
+    };
+    assert_eq!(
+        error(&rules.replace("opponent B paper", "opponent B papyrus")),
+        (
+            14,
+            12,
+            "expected rock, paper or scissors, found \"papyrus\"".to_string()
+        )
+    );
+    assert_eq!(
+        error(&rules.replace("score win 6", "score win six")).2,
+        "expected a score, found \"six\""
+    );
+    assert_eq!(
+        error(&rules.replace("score draw 3\n", "")).2,
+        "expected a score for draw"
+    );
+    assert_eq!(
+        error(&rules.replace("play Z scissors", "play Y scissors")).2,
+        "Y already has a meaning here"
+    );
+    assert_eq!(
+        error(&rules.replacen("scissors\n\n", "scissors well\n\n", 1)).0,
+        4
+    );
+    // Spelled out, every pair needs a winner.
+    let spelled_out = rules.replace(
+        "\nscore rock",
+        "\nbeats paper rock\nbeats rock scissors\nscore rock",
+    );
+    assert_eq!(
+        error(&spelled_out).2,
+        "expected paper or scissors to beat the other"
+    );
+    let spelled_out = spelled_out.replace("\nscore rock", "\nbeats scissors paper\nscore rock");
+    assert!(Rules::parse(&spelled_out).is_ok());
+}
@@ -0,0 +1,259 @@
+use crate::{as_play_score, Outcome, Rules};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    io,
+    str::FromStr,
+};
+
+/// Limits on the throws a strategy may play; `None` is no limit.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Constraints {
+    /// How many rounds may be lost.
+    pub max_losses: Option<usize>,
+    /// How many times each throw may be played, over the whole game.
+    pub max_uses: Option<usize>,
+    /// How many times in a row the same throw may be played (2 for "no three in a row").
+    pub max_run: Option<usize>,
+}
+
+impl Constraints {
+    /// Whether the rounds, as pairs of the opponent's throw and the throw played, keep within the
+    /// limits.
+    pub fn allow(&self, rules: &Rules, rounds: &[(usize, usize)]) -> bool {
+        let losses = (rounds.iter())
+            .filter(|&&(opponent, throw)| rules.outcome(throw, opponent) == Outcome::Lose)
+            .count();
+        let most_uses = (0..rules.throws())
+            .map(|throw| rounds.iter().filter(|&&(_, play)| play == throw).count())
+            .max();
+        let longest_run = (rounds.chunk_by(|(_, a), (_, b)| a == b))
+            .map(<[_]>::len)
+            .max();
+        let within = |limit: Option<usize>, value: Option<usize>| match (limit, value) {
+            (Some(limit), Some(value)) => value <= limit,
+            _ => true,
+        };
+        within(self.max_losses, Some(losses))
+            && within(self.max_uses, most_uses)
+            && within(self.max_run, longest_run)
+    }
+}
+
+impl FromStr for Constraints {
+    type Err = io::Error;
+
+    /// Reads limits such as `losses=3,uses=900,run=2` (any of them left out is no limit), or
+    /// `any` for none at all.
+    fn from_str(s: &str) -> io::Result<Self> {
+        let mut constraints = Constraints::default();
+        for limit in s
+            .split(',')
+            .filter(|limit| !limit.is_empty() && *limit != "any")
+        {
+            let invalid = || {
+                let message = format!(
+                    "expected losses=<n>, uses=<n> or run=<n>, found {:?}",
+                    limit
+                );
+                io::Error::new(io::ErrorKind::InvalidInput, message)
+            };
+            let (name, value) = limit.split_once('=').ok_or_else(invalid)?;
+            let value = Some(value.parse().map_err(|_| invalid())?);
+            match name {
+                "losses" => constraints.max_losses = value,
+                "uses" => constraints.max_uses = value,
+                "run" => constraints.max_run = value,
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(constraints)
+    }
+}
+
+/// The throws to play, and what they score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Strategy {
+    pub score: u32,
+    /// Each round's opponent's throw, and the throw to play against it.
+    pub rounds: Vec<(usize, usize)>,
+}
+
+/// What's left of the rounds played so far once the score is set aside: only what the
+/// constraints need, so that as many rounds as possible end up in the same state.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct State {
+    /// The last throw, and how many times in a row it was played.
+    run: Option<(usize, usize)>,
+    losses: usize,
+    /// How many times each throw was played.
+    uses: Vec<usize>,
+}
+
+impl Strategy {
+    /// The highest-scoring throws to play against the opponent's within the constraints, or
+    /// `None` if no throws at all are within them. Of several that score the same, which one is
+    /// given is arbitrary, but always the same.
+    ///
+    /// It's found exactly, by dynamic programming over the rounds, keeping the best score for each
+    /// `State` the rounds so far could end in. Without `max_uses`, that takes time linear in the
+    /// rounds; with it, the number of states grows with `max_uses` to the power of the number of
+    /// throws (less one), which soon gets slow.
+    pub fn best(rules: &Rules, opponents: &[usize], constraints: &Constraints) -> Option<Self> {
+        let counted = match constraints.max_uses {
+            Some(_) => rules.throws(),
+            None => 0,
+        };
+        let start = State {
+            run: None,
+            losses: 0,
+            uses: vec![0; counted],
+        };
+        let mut states = vec![(start, 0)];
+        // For each round, the state before it and the throw played, leading to each state.
+        let mut history: Vec<Vec<(usize, usize)>> = Vec::with_capacity(opponents.len());
+
+        for &opponent in opponents {
+            let mut next: Vec<(State, u32)> = vec![];
+            let mut links = vec![];
+            let mut index = HashMap::new();
+            for (before, (state, score)) in states.iter().enumerate() {
+                for throw in 0..rules.throws() {
+                    let Some(state) = step(rules, constraints, state, opponent, throw) else {
+                        continue;
+                    };
+                    let score = score + rules.score(throw, opponent);
+                    match index.entry(state) {
+                        Entry::Vacant(entry) => {
+                            next.push((entry.key().clone(), score));
+                            links.push((before, throw));
+                            entry.insert(next.len() - 1);
+                        }
+                        // Only strictly better, so that ties always go the same way.
+                        Entry::Occupied(entry) if score > next[*entry.get()].1 => {
+                            next[*entry.get()].1 = score;
+                            links[*entry.get()] = (before, throw);
+                        }
+                        Entry::Occupied(_) => {}
+                    }
+                }
+            }
+            states = next;
+            history.push(links);
+        }
+
+        let (mut state, &(_, best)) = (states.iter().enumerate())
+            .rev()
+            .max_by_key(|(_, (_, score))| *score)?;
+        let mut plays = vec![0; opponents.len()];
+        for (play, links) in plays.iter_mut().zip(&history).rev() {
+            (state, *play) = links[state];
+        }
+
+        let rounds: Vec<_> = opponents.iter().copied().zip(plays).collect();
+        let score = as_play_score(rules, &rounds);
+        assert_eq!(
+            score, best,
+            "the plays to score what the search found they would"
+        );
+        Some(Strategy { score, rounds })
+    }
+}
+
+/// The state after playing `throw` against the opponent's, if that keeps within the constraints.
+fn step(
+    rules: &Rules,
+    constraints: &Constraints,
+    state: &State,
+    opponent: usize,
+    throw: usize,
+) -> Option<State> {
+    let mut state = state.clone();
+    if let Some(max_run) = constraints.max_run {
+        let run = match state.run {
+            Some((last, run)) if last == throw => run + 1,
+            _ => 1,
+        };
+        if run > max_run {
+            return None;
+        }
+        state.run = Some((throw, run));
+    }
+    if let Some(max_losses) = constraints.max_losses {
+        if rules.outcome(throw, opponent) == Outcome::Lose {
+            state.losses += 1;
+            if state.losses > max_losses {
+                return None;
+            }
+        }
+    }
+    if let Some(max_uses) = constraints.max_uses {
+        state.uses[throw] += 1;
+        if state.uses[throw] > max_uses {
+            return None;
+        }
+    }
+    Some(state)
+}
+
+#[test]
+fn matches_brute_force() {
+    use aoc_check::{ints, vecs, Check};
+
+    let rules = Rules::default();
+    // The opponent's throws, and the limits, with the top of each range standing for no limit.
+    let cases = (vecs(ints(0, 2), 0), (ints(0, 4), (ints(0, 4), ints(1, 3))));
+    let constraints = |&(losses, (uses, run)): &(u64, (u64, u64))| Constraints {
+        max_losses: (losses < 4).then_some(losses as usize),
+        max_uses: (uses < 4).then_some(uses as usize),
+        max_run: (run < 3).then_some(run as usize),
+    };
+    Check::new().max_size(7).differential(
+        &cases,
+        |(opponents, limits)| {
+            let opponents: Vec<_> = opponents.iter().map(|&o| o as usize).collect();
+            let constraints = constraints(limits);
+            let best = Strategy::best(&rules, &opponents, &constraints)?;
+            assert!(constraints.allow(&rules, &best.rounds));
+            Some(best.score)
+        },
+        |(opponents, limits)| {
+            let opponents: Vec<_> = opponents.iter().map(|&o| o as usize).collect();
+            let constraints = constraints(limits);
+            // Every sequence of throws, counting in base 3.
+            (0..3usize.pow(opponents.len() as u32))
+                .map(|n| {
+                    (0..opponents.len())
+                        .map(|i| n / 3usize.pow(i as u32) % 3)
+                        .collect::<Vec<_>>()
+                })
+                .map(|plays| opponents.iter().copied().zip(plays).collect::<Vec<_>>())
+                .filter(|rounds| constraints.allow(&rules, rounds))
+                .map(|rounds| as_play_score(&rules, &rounds))
+                .max()
+        },
+    );
+}
+
+#[test]
+fn exampleinput() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    let rules = Rules::default();
+    let opponents = crate::opponents(input, &rules).unwrap();
+    // Paper beats rock, scissors beat paper, and rock beats scissors.
+    let best = Strategy::best(&rules, &opponents, &Constraints::default()).unwrap();
+    assert_eq!(best.score, 8 + 9 + 7);
+    assert_eq!(best.rounds, [(0, 1), (1, 2), (2, 0)]);
+
+    // Against rock every time, paper can't be played a third time in a row, so rock draws.
+    let constraints = "run=2".parse().unwrap();
+    let best = Strategy::best(&rules, &[0, 0, 0], &constraints).unwrap();
+    assert_eq!(best.score, 8 + 8 + 4);
+    assert!(constraints.allow(&rules, &best.rounds));
+    // Playing each throw once, only one round can be won.
+    let constraints = "uses=1,run=2".parse().unwrap();
+    let best = Strategy::best(&rules, &[0, 0, 0], &constraints).unwrap();
+    assert_eq!(best.score, 8 + 4 + 3);
+    let constraints = "uses=0".parse().unwrap();
+    assert!(Strategy::best(&rules, &[0, 0, 0], &constraints).is_none());
+    assert!("losses=x".parse::<Constraints>().is_err());
+}
@@ -0,0 +1,108 @@
+use crate::Rules;
+use std::cmp::{Ordering, Reverse};
+
+/// Two players' scores from playing their throws against each other, round by round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// The players, numbered in the order they were given.
+    pub players: (usize, usize),
+    pub scores: (u32, u32),
+}
+
+/// How a player did over the whole tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub player: usize,
+    pub won: usize,
+    pub drawn: usize,
+    pub lost: usize,
+    /// The score from every round of every match.
+    pub score: u64,
+}
+
+impl Standing {
+    /// As in a league: 3 for a match won and 1 for one drawn.
+    pub fn points(&self) -> usize {
+        self.won * 3 + self.drawn
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tournament {
+    pub matches: Vec<Match>,
+    /// Best first: by points, then by score, then in the order the players were given.
+    pub standings: Vec<Standing>,
+}
+
+impl Tournament {
+    /// Plays every player against every other once, each playing their throws in order. When one
+    /// has more throws than the other, the rounds past the end of the shorter aren't played.
+    pub fn round_robin(rules: &Rules, players: &[Vec<usize>]) -> Self {
+        let mut standings: Vec<_> = (0..players.len())
+            .map(|player| Standing {
+                player,
+                won: 0,
+                drawn: 0,
+                lost: 0,
+                score: 0,
+            })
+            .collect();
+        let mut matches = vec![];
+        for a in 0..players.len() {
+            for b in a + 1..players.len() {
+                let rounds = players[a].iter().zip(&players[b]);
+                let (mut score_a, mut score_b) = (0, 0);
+                for (&throw_a, &throw_b) in rounds {
+                    score_a += rules.score(throw_a, throw_b);
+                    score_b += rules.score(throw_b, throw_a);
+                }
+                for (player, score, other) in [(a, score_a, score_b), (b, score_b, score_a)] {
+                    let standing = &mut standings[player];
+                    standing.score += u64::from(score);
+                    match score.cmp(&other) {
+                        Ordering::Greater => standing.won += 1,
+                        Ordering::Equal => standing.drawn += 1,
+                        Ordering::Less => standing.lost += 1,
+                    }
+                }
+                matches.push(Match {
+                    players: (a, b),
+                    scores: (score_a, score_b),
+                });
+            }
+        }
+        // Stable, so that ties stay in the order the players were given.
+        standings.sort_by_key(|standing| Reverse((standing.points(), standing.score)));
+        Tournament { matches, standings }
+    }
+}
+
+#[test]
+fn round_robin() {
+    let rules = Rules::default();
+    let [rock, paper, scissors] = [0, 1, 2];
+    let players = [
+        vec![rock, rock],
+        vec![paper, paper],
+        vec![scissors, rock, paper],
+    ];
+    let tournament = Tournament::round_robin(&rules, &players);
+    let scores: Vec<_> = (tournament.matches.iter())
+        .map(|m| (m.players, m.scores))
+        .collect();
+    assert_eq!(
+        scores,
+        [((0, 1), (2, 16)), ((0, 2), (11, 7)), ((1, 2), (10, 10))]
+    );
+    let standings: Vec<_> = (tournament.standings.iter())
+        .map(|s| (s.player, s.won, s.drawn, s.lost, s.points(), s.score))
+        .collect();
+    assert_eq!(
+        standings,
+        [
+            (1, 1, 1, 0, 4, 26),
+            (0, 1, 0, 1, 3, 13),
+            (2, 0, 1, 1, 1, 17)
+        ]
+    );
+}
@@ -0,0 +1,77 @@
+use crate::{Outcome, Rules};
+use std::io::{self, Write};
+
+/// A round of the game, as it was played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub opponent: usize,
+    pub throw: usize,
+    pub outcome: Outcome,
+    pub points: u32,
+    /// The points of this round and every one before it.
+    pub total: u32,
+}
+
+/// The rounds, given as pairs of the opponent's throw and the throw played, one by one.
+pub(crate) fn transcript(rules: &Rules, rounds: &[(usize, usize)]) -> Vec<Round> {
+    let mut total = 0;
+    (rounds.iter())
+        .map(|&(opponent, throw)| {
+            let points = rules.score(throw, opponent);
+            total += points;
+            Round {
+                opponent,
+                throw,
+                outcome: rules.outcome(throw, opponent),
+                points,
+                total,
+            }
+        })
+        .collect()
+}
+
+/// Writes the rounds as CSV, with a header and the throws by name.
+pub fn write_csv(rules: &Rules, rounds: &[Round], mut out: impl Write) -> io::Result<()> {
+    writeln!(out, "round,opponent,throw,outcome,points,total")?;
+    for (number, round) in rounds.iter().enumerate() {
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            number + 1,
+            csv_field(rules.name(round.opponent)),
+            csv_field(rules.name(round.throw)),
+            round.outcome.name(),
+            round.points,
+            round.total
+        )?;
+    }
+    Ok(())
+}
+
+/// The field, quoted if it has to be.
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+#[test]
+fn exampleinput() {
+    let rules = Rules::default();
+    let input = std::fs::read("exampleinput").unwrap();
+    let rounds = crate::transcript(&input[..], &rules, crate::Column::Play).unwrap();
+    let totals: Vec<_> = rounds.iter().map(|round| round.total).collect();
+    assert_eq!(totals, [8, 9, 15]);
+
+    let mut csv = vec![];
+    write_csv(&rules, &rounds, &mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "round,opponent,throw,outcome,points,total\n\
+         1,rock,paper,win,8,8\n\
+         2,paper,rock,lose,1,9\n\
+         3,scissors,scissors,draw,6,15\n"
+    );
+    assert_eq!(csv_field("a \"b\", c"), "\"a \"\"b\"\", c\"");
+}
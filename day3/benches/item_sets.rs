@@ -0,0 +1,60 @@
+//! Times the `ItemSet` solvers against the sorted ones they replaced, on ever larger generated
+//! inputs: `cargo bench -p day3 --features sorted`.
+
+use aoc_core::Rng;
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+type Solver = fn(&[u8]) -> aoc_core::Result<u32>;
+
+/// The median time the solver takes over the input, and its answer.
+fn time(solver: Solver, input: &[u8]) -> (Duration, u32) {
+    let answer = solver(input).expect("the generated input to be valid");
+    let mut times: Vec<_> = (0..11)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(solver(black_box(input)).unwrap());
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    (times[times.len() / 2], answer)
+}
+
+fn main() {
+    let parts: [(&str, Solver, Solver); 2] = [
+        (
+            "1",
+            |input| day3::solve_part1(input),
+            |input| day3::sorted::solve_part1(input),
+        ),
+        (
+            "2",
+            |input| day3::solve_part2(input),
+            |input| day3::sorted::solve_part2(input),
+        ),
+    ];
+    println!("Rucksacks  Part      ItemSet       Sorted  Speedup");
+    for rucksacks in [1_000, 10_000, 100_000, 1_000_000] {
+        let input = day3::generate(&mut Rng::new(42), rucksacks);
+        for (part, item_sets, sorted) in parts {
+            let (item_sets, answer) = time(item_sets, input.as_bytes());
+            let (sorted, sorted_answer) = time(sorted, input.as_bytes());
+            assert_eq!(
+                answer, sorted_answer,
+                "part {} over {} rucksacks",
+                part, rucksacks
+            );
+            println!(
+                "{:>9}  {:>4}  {:>11.3?}  {:>11.3?}  {:>6.2}x",
+                rucksacks,
+                part,
+                item_sets,
+                sorted,
+                sorted.as_secs_f64() / item_sets.as_secs_f64()
+            );
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use aoc_core::Rng;
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Random rucksacks, `rucksacks` of them rounded up to a whole number of groups of three. Each
+/// rucksack has exactly one item in both compartments, and each group exactly one item in common.
+pub fn generate(rng: &mut Rng, rucksacks: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..rucksacks.div_ceil(3).max(1) {
+        let badge = *rng.choose(ITEMS);
+        // Every other item is kept out of one rucksack of the group, so that only the badge can
+        // be common to all three.
+        let left_out: Vec<(u8, usize)> = ITEMS
+            .iter()
+            .filter(|&&item| item != badge)
+            .map(|&item| (item, rng.index(3)))
+            .collect();
+        for rucksack in 0..3 {
+            let mut allowed: Vec<u8> = left_out
+                .iter()
+                .filter(|&&(_, out)| out != rucksack)
+                .map(|&(item, _)| item)
+                .collect();
+            rng.shuffle(&mut allowed);
+            let duplicate = allowed.pop().unwrap();
+            // Split the rest between the compartments, so they share nothing but the duplicate.
+            let (left, right) = allowed.split_at(allowed.len() / 2);
+            let size = rng.between(2, 16) as usize;
+            let mut compartments = [vec![duplicate], vec![duplicate]];
+            compartments[rng.index(2)].push(badge);
+            for (compartment, others) in compartments.iter_mut().zip([left, right]) {
+                while compartment.len() < size {
+                    compartment.push(*rng.choose(others));
+                }
+                rng.shuffle(compartment);
+                input.extend(compartment.iter().map(|&item| char::from(item)));
+            }
+            input.push('\n');
+        }
+    }
+    input
+}
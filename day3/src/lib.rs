@@ -0,0 +1,329 @@
+mod generate;
+mod item_set;
+#[cfg(feature = "sorted")]
+pub mod sorted;
+
+pub use generate::generate;
+pub use item_set::ItemSet;
+
+use aoc_core::{ParseError, Result};
+use std::io::{self, BufRead, BufReader, Read};
+
+/// An item, as the letter it's written as: always an ASCII letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemType {
+    inner: u8,
+}
+
+impl ItemType {
+    /// The item written as `letter`, unless it isn't an ASCII letter.
+    pub fn new(letter: u8) -> Option<Self> {
+        letter
+            .is_ascii_alphabetic()
+            .then_some(ItemType { inner: letter })
+    }
+
+    /// The item written as `letter`, which the caller has already checked is an ASCII letter.
+    pub(crate) fn from_letter(letter: u8) -> Self {
+        debug_assert!(letter.is_ascii_alphabetic());
+        ItemType { inner: letter }
+    }
+
+    /// The item with the priority, from 1 (`a`) to 52 (`Z`).
+    fn from_numeric(priority: u8) -> Self {
+        match priority {
+            1..=26 => ItemType::from_letter(b'a' + priority - 1),
+            _ => ItemType::from_letter(b'A' + priority - 27),
+        }
+    }
+
+    pub fn ascii(&self) -> u8 {
+        self.inner
+    }
+
+    /// The item's priority: 1 to 26 for `a` to `z`, and 27 to 52 for `A` to `Z`.
+    pub fn numeric(&self) -> u8 {
+        let ascii = self.inner;
+        if ascii > 96 {
+            ascii - 96
+        } else {
+            ascii - 64 + 26
+        }
+    }
+}
+
+impl PartialOrd for ItemType {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ItemType {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.numeric().cmp(&other.numeric())
+    }
+}
+
+/// A rucksack's compartments, as the sets of the items in each.
+struct Rucksack {
+    left: ItemSet,
+    right: ItemSet,
+}
+
+impl Rucksack {
+    fn new(items: &str) -> Self {
+        let (left, right) = items.as_bytes().split_at(items.len() / 2);
+        Rucksack {
+            left: ItemSet::from_letters(left),
+            right: ItemSet::from_letters(&right[..left.len()]),
+        }
+    }
+
+    /// The item in both compartments (the one with the lowest priority, if there are several).
+    fn duplicate(&self) -> Option<ItemType> {
+        (self.left & self.right).first()
+    }
+}
+
+/// Rucksacks carried together, whose badge is the one item they all hold.
+struct Group<'r> {
+    rucksacks: &'r [ItemSet],
+}
+
+impl<'r> From<&'r [ItemSet]> for Group<'r> {
+    fn from(rucksacks: &'r [ItemSet]) -> Self {
+        Group { rucksacks }
+    }
+}
+
+impl Group<'_> {
+    /// The item in every rucksack (the one with the lowest priority, if there are several).
+    fn common(&self) -> Option<ItemType> {
+        let common = (self.rucksacks.iter()).fold(ItemSet::ALL, |common, &items| common & items);
+        common.first()
+    }
+}
+
+/// How the rucksacks are grouped, in the order they're listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grouping {
+    /// Every `n` rucksacks in turn, as the elves' groups are.
+    Chunks(usize),
+    /// Every `n` rucksacks in a row, the groups overlapping.
+    Windows(usize),
+}
+
+impl Grouping {
+    /// The groups, each with the index of its first rucksack.
+    fn groups<T>(self, rucksacks: &[T]) -> Box<dyn Iterator<Item = (usize, &[T])> + '_> {
+        match self {
+            Grouping::Chunks(n) => Box::new(
+                (rucksacks.chunks_exact(n).enumerate()).map(move |(i, group)| (i * n, group)),
+            ),
+            Grouping::Windows(n) => Box::new(rucksacks.windows(n).enumerate()),
+        }
+    }
+}
+
+/// Reads every rucksack, checking that each holds only items.
+fn rucksacks(input: impl Read) -> Result<Vec<String>> {
+    let lines = BufReader::new(input)
+        .lines()
+        .collect::<io::Result<Vec<_>>>()?;
+    for (line_no, line) in lines.iter().enumerate() {
+        check_items(line, line_no)?;
+    }
+    Ok(lines)
+}
+
+/// Checks that a rucksack only holds items (i.e., letters).
+fn check_items(line: &str, line_no: usize) -> Result<(), ParseError> {
+    match line.chars().position(|c| !c.is_ascii_alphabetic()) {
+        Some(column) => Err(ParseError::new(
+            "expected an item (a letter)",
+            line_no + 1,
+            column + 1,
+            line,
+        )),
+        None => Ok(()),
+    }
+}
+
+#[test]
+fn exampleinput() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part1(input).unwrap(), 157);
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part2(input).unwrap(), 70);
+}
+
+#[test]
+fn duplicate_matches_naive_search() {
+    use aoc_check::{strings, Check};
+
+    Check::new().differential(
+        &strings("abcdeABCDE", 0),
+        |items| Rucksack::new(items).duplicate().map(|item| item.inner),
+        |items| {
+            let (left, right) = items.as_bytes().split_at(items.len() / 2);
+            let right = &right[..left.len()];
+            (left.iter().copied())
+                .filter(|c| right.contains(c))
+                .min_by_key(|&c| ItemType::from_letter(c).numeric())
+        },
+    );
+}
+
+#[test]
+fn common_matches_naive_search() {
+    use aoc_check::{strings, vecs, Check};
+
+    Check::new().differential(
+        &vecs(strings("abcdeABCDE", 0), 1),
+        |group| {
+            let rucksacks: Vec<_> = (group.iter())
+                .map(|items| ItemSet::from_letters(items.as_bytes()))
+                .collect();
+            Group::from(&rucksacks[..]).common().map(|item| item.inner)
+        },
+        |group| {
+            let in_all = |c: &u8| group[1..].iter().all(|items| items.as_bytes().contains(c));
+            (group[0].bytes())
+                .filter(in_all)
+                .min_by_key(|&c| ItemType::from_letter(c).numeric())
+        },
+    );
+}
+
+#[test]
+fn badges_match_naive_search() {
+    use aoc_check::{ints, strings, vecs, Check};
+
+    // Few items, so that groups often have one in common; either way, the answer or the first
+    // group without one.
+    let cases = (vecs(strings("abAB", 1), 0), (ints(1, 4), ints(0, 1)));
+    let grouping = |&(n, windows): &(u64, u64)| match windows {
+        0 => Grouping::Chunks(n as usize),
+        _ => Grouping::Windows(n as usize),
+    };
+    Check::new().differential(
+        &cases,
+        |(lines, grouping_case)| {
+            let input = lines
+                .iter()
+                .map(|line| format!("{}\n", line))
+                .collect::<String>();
+            let answer = badges(input.as_bytes(), grouping(grouping_case));
+            answer.map_err(|e| e.as_parse().unwrap().line())
+        },
+        |(lines, grouping_case)| {
+            let (n, starts): (usize, Vec<usize>) = match grouping(grouping_case) {
+                Grouping::Chunks(n) if lines.len() % n != 0 => {
+                    return Err(lines.len() - lines.len() % n + 1)
+                }
+                Grouping::Chunks(n) => (n, (0..lines.len()).step_by(n).collect()),
+                Grouping::Windows(n) => (n, (0..(lines.len() + 1).saturating_sub(n)).collect()),
+            };
+            let mut sum = 0;
+            for start in starts {
+                let group = &lines[start..start + n];
+                let common = (b'a'..=b'z')
+                    .chain(b'A'..=b'Z')
+                    .find(|c| group.iter().all(|items| items.as_bytes().contains(c)));
+                sum += ItemType::from_letter(common.ok_or(start + 1)?).numeric() as u32;
+            }
+            Ok(sum)
+        },
+    );
+}
+
+#[test]
+fn malformed() {
+    let error = solve_part1("vJrwpWtwJgWrhcsFMMfFFhFp\nabc-ab\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (2, 4));
+
+    let error = solve_part2("abc\ndef\nghi\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (1, 1));
+
+    for grouping in [Grouping::Chunks(0), Grouping::Windows(0)] {
+        let error = badges("abc\n".as_bytes(), grouping).unwrap_err();
+        assert!(error.as_parse().is_none());
+    }
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub fn parse(input: impl Read) -> Result<impl Sized> {
+    rucksacks(input)
+}
+
+/// The sum of the priorities of the item in both compartments of each rucksack.
+pub fn solve_part1(input: impl Read) -> Result<u32> {
+    sum_duplicates(input, |items| Rucksack::new(items).duplicate())
+}
+
+/// The sum of the priorities of the badge common to each group of three rucksacks.
+pub fn solve_part2(input: impl Read) -> Result<u32> {
+    badges(input, Grouping::Chunks(3))
+}
+
+/// The sum of the priorities of the item common to each group of rucksacks.
+pub fn badges(input: impl Read, grouping: Grouping) -> Result<u32> {
+    sum_badges(
+        input,
+        grouping,
+        |items| ItemSet::from_letters(items.as_bytes()),
+        |group| Group::from(group).common(),
+    )
+}
+
+/// `solve_part1`, finding the item in both compartments with `duplicate`.
+fn sum_duplicates(input: impl Read, duplicate: impl Fn(&str) -> Option<ItemType>) -> Result<u32> {
+    let mut duplicates_sum: u32 = 0;
+    for (line_no, line) in BufReader::new(input).lines().enumerate() {
+        let line = line?;
+        check_items(&line, line_no)?;
+        if line.len() % 2 != 0 {
+            let message = "expected an even number of items, to split into two compartments";
+            return Err(ParseError::new(message, line_no + 1, 1, &line).into());
+        }
+        let duplicate = duplicate(&line).ok_or_else(|| {
+            ParseError::new("no item is in both compartments", line_no + 1, 1, &line)
+        })?;
+        duplicates_sum += duplicate.numeric() as u32;
+    }
+    Ok(duplicates_sum)
+}
+
+/// `badges`, with each rucksack as `rucksack` makes it, finding the item common to each group
+/// with `common`.
+fn sum_badges<T>(
+    input: impl Read,
+    grouping: Grouping,
+    rucksack: impl Fn(&str) -> T,
+    common: impl Fn(&[T]) -> Option<ItemType>,
+) -> Result<u32> {
+    let (Grouping::Chunks(n) | Grouping::Windows(n)) = grouping;
+    if n == 0 {
+        let message = "expected groups of at least one rucksack";
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, message).into());
+    }
+    let lines = rucksacks(input)?;
+    if matches!(grouping, Grouping::Chunks(_)) && lines.len() % n != 0 {
+        let line_no = lines.len() - lines.len() % n;
+        let message = format!("expected the rucksacks to come in groups of {}", n);
+        return Err(ParseError::new(message, line_no + 1, 1, &lines[line_no]).into());
+    }
+
+    let rucksacks: Vec<T> = lines.iter().map(|line| rucksack(line)).collect();
+    let mut common_sum = 0;
+    for (first, group) in grouping.groups(&rucksacks) {
+        let common = common(group).ok_or_else(|| {
+            let message = format!("no item is common to this group of {}", n);
+            ParseError::new(message, first + 1, 1, &lines[first])
+        })?;
+        common_sum += common.numeric() as u32;
+    }
+    Ok(common_sum)
+}
@@ -0,0 +1,225 @@
+//! The rucksacks as they were first solved: each compartment (or rucksack) insertion-sorted by
+//! ASCII code, and the sorted lists walked in lockstep until they meet. It's kept to compare the
+//! `ItemSet`s against (see `benches/item_sets.rs`); of several items in common, it finds the one
+//! with the lowest ASCII code rather than the lowest priority.
+
+use crate::ItemType;
+use aoc_core::Result;
+use std::io::Read;
+
+struct Rucksack<'items> {
+    items: &'items [u8],
+    /// Sorted by ASCII code, not ItemType::numeric().
+    left_sorted: Vec<usize>,
+    /// Sorted by ASCII code, not ItemType::numeric().
+    right_sorted: Vec<usize>,
+}
+
+impl<'i> Rucksack<'i> {
+    fn new(items: &'i str) -> Self {
+        let items = items.as_bytes();
+
+        // Sorting (with deduplication) is O(n log(n)), and then finding a duplicate is just O(n)
+        // making the whole thing Õ(n). Comparing each item in the first half to each item in the
+        // second half would be O(n²).
+        // (We pay O(n) of memory for it.)
+
+        let mut left_sorted: Vec<usize> = vec![];
+        let mut right_sorted: Vec<usize> = vec![];
+
+        let section_size = items.len() / 2;
+        // This iterator will iterate over pairs of (index, &elem) for each section.
+        let left_and_right_iterator = (0..section_size)
+            .zip(section_size..)
+            .map(|(li, ri)| ((li, &items[li]), (ri, &items[ri])));
+
+        for ((li, lc), (ri, rc)) in left_and_right_iterator {
+            // Insert sorted for the left section
+            match left_sorted.binary_search_by(|&other| items[other].cmp(lc)) {
+                Ok(_) => { /* De-duplicate */ }
+                Err(insert_at) => {
+                    left_sorted.insert(insert_at, li);
+                }
+            }
+
+            // Insert sorted for the right section
+            match right_sorted.binary_search_by(|&other| items[other].cmp(rc)) {
+                Ok(_) => { /* De-duplicate */ }
+                Err(insert_at) => {
+                    right_sorted.insert(insert_at, ri);
+                }
+            }
+        }
+
+        Rucksack {
+            items,
+            left_sorted,
+            right_sorted,
+        }
+    }
+
+    fn duplicate(&self) -> Option<ItemType> {
+        // Lockstep
+        let mut left_ptr = 0;
+        let mut right_ptr = 0;
+        while left_ptr < self.left_sorted.len() && right_ptr < self.right_sorted.len() {
+            let left = self.items[self.left_sorted[left_ptr]];
+            let right = self.items[self.right_sorted[right_ptr]];
+
+            match left.cmp(&right) {
+                std::cmp::Ordering::Equal => {
+                    return Some(ItemType::from_letter(left));
+                }
+                std::cmp::Ordering::Less => {
+                    left_ptr += 1;
+                }
+                std::cmp::Ordering::Greater => {
+                    right_ptr += 1;
+                }
+            }
+        }
+        None
+    }
+}
+
+struct Group {
+    lines: [Vec<u8>; 3],
+}
+
+impl From<[&str; 3]> for Group {
+    fn from(lines: [&str; 3]) -> Self {
+        Group {
+            // FIXME: There's some unsafe efficiency trickery to be done here.
+            lines: [
+                lines[0].bytes().collect(),
+                lines[1].bytes().collect(),
+                lines[2].bytes().collect(),
+            ],
+        }
+    }
+}
+
+impl Group {
+    fn common(&self) -> Option<ItemType> {
+        // Same logic as in the `Rucksack`, but for groups of three now
+        // (and each line being its own entry, instead of two).
+        let mut insert_sorted = [vec![], vec![], vec![]];
+
+        let mut insert_sort_deduplicate = |group: usize, index: usize, c: u8| {
+            let insert_sorted = &mut insert_sorted[group];
+            let line: &[u8] = &self.lines[group];
+            match insert_sorted.binary_search_by(|&other: &usize| line[other].cmp(&c)) {
+                Ok(_) => { /* De-duplicate */ }
+                Err(insert_at) => {
+                    insert_sorted.insert(insert_at, index);
+                }
+            }
+        };
+
+        // It's not gorgeous, but probably the way to write this with least duplication!
+        for (i1, c1) in self.lines[0].iter().enumerate() {
+            insert_sort_deduplicate(0, i1, *c1);
+        }
+        for (i2, c2) in self.lines[1].iter().enumerate() {
+            insert_sort_deduplicate(1, i2, *c2);
+        }
+        for (i3, c3) in self.lines[2].iter().enumerate() {
+            insert_sort_deduplicate(2, i3, *c3);
+        }
+
+        // Same lockstep algorithm as before
+        // The difference is we move forward the smallest entry (/entries)
+        let mut ptr = [0, 0, 0];
+        macro_rules! in_bounds {
+            ($group: literal) => {{
+                ptr[$group] < insert_sorted[$group].len()
+            }};
+        }
+        while in_bounds!(0) && in_bounds!(1) && in_bounds!(2) {
+            let first: u8 = self.lines[0][insert_sorted[0][ptr[0]]];
+            let second: u8 = self.lines[1][insert_sorted[1][ptr[1]]];
+            let third: u8 = self.lines[2][insert_sorted[2][ptr[2]]];
+            if first == second && second == third {
+                return Some(ItemType::from_letter(first));
+            }
+
+            let min = std::cmp::min(std::cmp::min(first, second), third);
+            if first == min && ptr[0] < insert_sorted[0].len() {
+                ptr[0] += 1;
+            }
+            if second == min && ptr[1] < insert_sorted[1].len() {
+                ptr[1] += 1;
+            }
+            if third == min && ptr[2] < insert_sorted[2].len() {
+                ptr[2] += 1;
+            }
+        }
+        None
+    }
+}
+
+/// `crate::solve_part1`, with the sorted compartments.
+pub fn solve_part1(input: impl Read) -> Result<u32> {
+    crate::sum_duplicates(input, |items| Rucksack::new(items).duplicate())
+}
+
+/// `crate::solve_part2`, with the sorted rucksacks.
+pub fn solve_part2(input: impl Read) -> Result<u32> {
+    crate::sum_badges(input, crate::Grouping::Chunks(3), str::to_string, |group| {
+        Group::from([group[0].as_str(), &group[1], &group[2]]).common()
+    })
+}
+
+#[test]
+fn duplicate_matches_naive_search() {
+    use aoc_check::{strings, Check};
+
+    Check::new().differential(
+        &strings("abcdeABCDE", 0),
+        |items| Rucksack::new(items).duplicate().map(|item| item.inner),
+        |items| {
+            let (left, right) = items.as_bytes().split_at(items.len() / 2);
+            let right = &right[..left.len()];
+            left.iter().copied().filter(|c| right.contains(c)).min()
+        },
+    );
+}
+
+#[test]
+fn common_matches_naive_search() {
+    use aoc_check::{strings, Check};
+
+    let items = || strings("abcdeABCDE", 0);
+    Check::new().differential(
+        &((items(), items()), items()),
+        |((first, second), third)| {
+            let group = Group::from([first.as_str(), second, third]);
+            group.common().map(|item| item.inner)
+        },
+        |((first, second), third)| {
+            let in_all = |c: &u8| second.as_bytes().contains(c) && third.as_bytes().contains(c);
+            first.bytes().filter(in_all).min()
+        },
+    );
+}
+
+#[test]
+fn matches_item_sets() {
+    use aoc_check::{ints, Check};
+    use aoc_core::Rng;
+
+    // Valid inputs have a single item in common, so both find the same.
+    Check::new().cases(50).differential(
+        &ints(0, u64::MAX),
+        |&seed| {
+            let input = crate::generate(&mut Rng::new(seed), 30);
+            let part1 = solve_part1(input.as_bytes()).unwrap();
+            (part1, solve_part2(input.as_bytes()).unwrap())
+        },
+        |&seed| {
+            let input = crate::generate(&mut Rng::new(seed), 30);
+            let part1 = crate::solve_part1(input.as_bytes()).unwrap();
+            (part1, crate::solve_part2(input.as_bytes()).unwrap())
+        },
+    );
+}
@@ -0,0 +1,16 @@
+use aoc_core::Rng;
+
+/// A random list of `pairs` pairs of section ranges.
+pub fn generate(rng: &mut Rng, pairs: usize) -> String {
+    let mut input = String::new();
+    let range = |rng: &mut Rng| {
+        let start = rng.between(1, 99);
+        (start, rng.between(start, 99))
+    };
+    for _ in 0..pairs {
+        let (a, b) = range(rng);
+        let (c, d) = range(rng);
+        input.push_str(&format!("{}-{},{}-{}\n", a, b, c, d));
+    }
+    input
+}
@@ -0,0 +1,147 @@
+mod generate;
+
+pub use generate::generate;
+
+use aoc_core::Result;
+use aoc_parse::{Parser, Source};
+use std::io::{BufReader, Read};
+
+type SizeOrderedRange<'r> = (&'r Range, &'r Range);
+
+struct Range {
+    start: u32,
+    end: u32,
+}
+
+impl Range {
+    fn new(start: u32, end: u32) -> Self {
+        debug_assert!(end >= start);
+        Range { start, end }
+    }
+
+    fn parser(parser: &mut Parser<impl Source>) -> Result<Self> {
+        let range_start = parser.position();
+        let start = parser.number()?;
+        parser.match_('-')?;
+        let end = parser.number()?;
+        if end < start {
+            return Err(parser.error_at("range ends before it starts", range_start));
+        }
+        Ok(Self::new(start, end))
+    }
+
+    fn intersects(&self, other: &Self) -> bool {
+        if self.start > other.end {
+            return false;
+        }
+        if self.end < other.start {
+            return false;
+        }
+        true
+    }
+
+    fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    fn size(&self) -> u32 {
+        self.end - self.start
+    }
+
+    /// Returns the references in ascending order of size.
+    fn size_pair<'a>(&'a self, other: &'a Self) -> SizeOrderedRange<'a> {
+        if self.size() < other.size() {
+            (self, other)
+        } else {
+            (other, self)
+        }
+    }
+}
+
+trait ForSizeOrderedRange {
+    fn containing(&self) -> bool;
+}
+
+impl ForSizeOrderedRange for SizeOrderedRange<'_> {
+    fn containing(&self) -> bool {
+        self.1.contains(self.0)
+    }
+}
+
+#[test]
+fn range_intersections() {
+    assert!(Range::new(6, 8).intersects(&Range::new(5, 9)));
+    assert!(Range::new(6, 8).intersects(&Range::new(5, 6)));
+    assert!(Range::new(6, 8).intersects(&Range::new(5, 7)));
+    assert!(Range::new(2, 6).intersects(&Range::new(3, 7)));
+    assert!(!Range::new(6, 10).intersects(&Range::new(2, 5)));
+    assert!(!Range::new(2, 5).intersects(&Range::new(6, 9)));
+}
+
+/// Reads every pair of ranges.
+fn pairs(input: impl Read) -> Result<Vec<(Range, Range)>> {
+    let mut pairs = vec![];
+    let mut parser = Parser::from_reader(BufReader::new(input));
+    loop {
+        parser.skip_whitespace();
+        if parser.done()? {
+            break;
+        }
+        let first = Range::parser(&mut parser)?;
+        parser.match_(',')?;
+        let second = Range::parser(&mut parser)?;
+        parser.skip_spaces();
+        parser.end_of_line()?;
+        pairs.push((first, second));
+    }
+    Ok(pairs)
+}
+
+/// Counts the pairs where one range contains the other, and the pairs where the ranges overlap.
+fn count_pairs(input: impl Read) -> Result<(u32, u32)> {
+    let mut containing_pairs = 0;
+    let mut overlapping_pairs = 0;
+    for (first, second) in pairs(input)? {
+        if first.size_pair(&second).containing() {
+            containing_pairs += 1;
+        }
+        if first.intersects(&second) {
+            overlapping_pairs += 1;
+        }
+    }
+    Ok((containing_pairs, overlapping_pairs))
+}
+
+#[test]
+fn exampleinput() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part1(input).unwrap(), 2);
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part2(input).unwrap(), 4);
+}
+
+#[test]
+fn malformed() {
+    let error = solve_part1("2-4,6-8\n2-3;4-5\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (2, 4));
+
+    let error = solve_part1("2-4,8-6\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (1, 5));
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub fn parse(input: impl Read) -> Result<impl Sized> {
+    pairs(input)
+}
+
+/// The number of pairs where one range fully contains the other.
+pub fn solve_part1(input: impl Read) -> Result<u32> {
+    Ok(count_pairs(input)?.0)
+}
+
+/// The number of pairs where the ranges overlap at all.
+pub fn solve_part2(input: impl Read) -> Result<u32> {
+    Ok(count_pairs(input)?.1)
+}
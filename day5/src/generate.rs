@@ -0,0 +1,47 @@
+use aoc_core::Rng;
+
+/// A random drawing of up to nine piles of crates, followed by `moves` moves that can all be
+/// carried out.
+pub fn generate(rng: &mut Rng, moves: usize) -> String {
+    let number_of_piles = rng.between(2, 9) as usize;
+    let mut piles: Vec<Vec<char>> = (0..number_of_piles)
+        .map(|_| {
+            (0..rng.between(0, 8))
+                .map(|_| char::from(b'A' + rng.below(26) as u8))
+                .collect()
+        })
+        .collect();
+    if piles.iter().all(|pile| pile.is_empty()) {
+        piles[0].push('A');
+    }
+
+    let mut input = String::new();
+    let height = piles.iter().map(|pile| pile.len()).max().unwrap();
+    for row in (0..height).rev() {
+        let cells: Vec<String> = piles
+            .iter()
+            .map(|pile| match pile.get(row) {
+                Some(c) => format!("[{}]", c),
+                None => "   ".to_string(),
+            })
+            .collect();
+        input.push_str(&cells.join(" "));
+        input.push('\n');
+    }
+    let numbers: Vec<String> = (1..=number_of_piles).map(|n| format!(" {} ", n)).collect();
+    input.push_str(&numbers.join(" "));
+    input.push_str("\n\n");
+
+    // Only the sizes of the piles matter from here on.
+    let mut sizes: Vec<usize> = piles.iter().map(|pile| pile.len()).collect();
+    for _ in 0..moves {
+        let non_empty: Vec<usize> = (0..number_of_piles).filter(|&i| sizes[i] > 0).collect();
+        let from = *rng.choose(&non_empty);
+        let to = (from + rng.between(1, number_of_piles as u64 - 1) as usize) % number_of_piles;
+        let count = rng.between(1, sizes[from] as u64) as usize;
+        sizes[from] -= count;
+        sizes[to] += count;
+        input.push_str(&format!("move {} from {} to {}\n", count, from + 1, to + 1));
+    }
+    input
+}
@@ -0,0 +1,212 @@
+mod generate;
+
+pub use generate::generate;
+
+use aoc_core::{ParseError, Result};
+use aoc_parse::Parser;
+use std::{
+    collections::VecDeque,
+    io::{self, Read},
+};
+
+#[derive(Clone)]
+struct Piles {
+    piles: Vec<VecDeque<char>>,
+}
+
+impl Piles {
+    fn new(count: usize) -> Self {
+        Piles {
+            piles: vec![VecDeque::new(); count],
+        }
+    }
+
+    fn put(&mut self, on: usize, value: char) {
+        self.piles[on].push_front(value);
+    }
+
+    fn shuffle(&mut self, count: u32, from: usize, to: usize) {
+        for _ in 0..count {
+            let to_move = self.piles[from].pop_back().expect("enough items to exist to be moved");
+            self.piles[to].push_back(to_move);
+        }
+    }
+
+    fn move_(&mut self, count: u32, from: usize, to: usize) {
+        let size = self.piles[from].len();
+        let moved: Vec<char> = self.piles[from].drain((size - count as usize)..).collect();
+        for element in moved {
+            self.piles[to].push_back(element);
+        }
+    }
+
+    /// The crates at the top of each pile, with a space for empty piles.
+    fn tops(&self) -> String {
+        self.piles
+            .iter()
+            .map(|pile| pile.back().copied().unwrap_or(' '))
+            .collect()
+    }
+}
+
+/// Parses the (1-based) number of a pile, returning its index.
+fn pile_index(parser: &mut Parser<&str>, number_of_piles: usize) -> Result<usize> {
+    let start = parser.position();
+    match parser.number()? {
+        n @ 1.. if n <= number_of_piles => Ok(n - 1),
+        n => Err(parser.error_at(format!("there is no pile {}", n), start)),
+    }
+}
+
+/// A step of the rearrangement procedure.
+struct Move {
+    count: u32,
+    from: usize,
+    to: usize,
+    /// The (1-based) line the step is on, for error reports.
+    line: usize,
+}
+
+/// Reads the starting piles, and the steps of the rearrangement procedure.
+fn procedure(input: &str) -> Result<(Piles, Vec<Move>)> {
+    // Read the number of piles, and the height of the drawing above the pile numbers.
+    // This is the easiest way I could think of doing it.
+    let (number_of_piles, number_of_rows): (usize, usize) = {
+        let mut number = 0;
+        let mut rows = 0;
+        for line in input.lines() {
+            let mut parser = Parser::new(line);
+            parser.eat_predicate(|c| c.is_whitespace());
+            if parser.try_match_predicate(|c| c.is_numeric()).is_some() {
+                number = 1;
+                while {
+                    parser.eat_predicate(|c| c.is_whitespace());
+                    parser.try_match_predicate(|c| c.is_numeric()).is_some()
+                } {
+                    number += 1;
+                }
+                break;
+            }
+            rows += 1;
+        }
+        (number, rows)
+    };
+    if number_of_piles == 0 {
+        return Err(ParseError::at_end("expected a line numbering the piles", input).into());
+    }
+
+    // Knowing the number of piles, parse the elements of the piles
+    let mut piles = Piles::new(number_of_piles);
+    let mut parser = Parser::new(input);
+
+    // Leading whitespace is meaningful here (it stands for empty spots), so we can't skip it.
+    for _ in 0..number_of_rows {
+        for pile in 0..number_of_piles {
+            if parser.try_match('[') {
+                match parser.take() {
+                    Some(c) => piles.put(pile, c),
+                    None => return Err(parser.error("expected a crate")),
+                }
+                parser.match_(']')?;
+            } else {
+                parser.match_str("   ")?;
+            }
+
+            if !parser.try_match(' ') {
+                parser.try_match('\r');
+                parser.match_('\n')?;
+            }
+        }
+    }
+
+    // Eat until the first line of "move"
+    parser.eat_predicate(|c| c != 'm');
+
+    let mut moves = vec![];
+    while !parser.eof() {
+        let line = parser.position().line;
+        parser.match_str("move ")?;
+        let count = parser.number()?;
+        parser.match_str(" from ")?;
+        let from = pile_index(&mut parser, number_of_piles)?;
+        parser.match_str(" to ")?;
+        let to = pile_index(&mut parser, number_of_piles)?;
+        parser.skip_spaces();
+        parser.end_of_line()?;
+        parser.skip_whitespace();
+        moves.push(Move {
+            count,
+            from,
+            to,
+            line,
+        });
+    }
+
+    Ok((piles, moves))
+}
+
+/// Rearranges the crates with both crane models, returning the piles after the CrateMover 9000
+/// and the CrateMover 9001 have finished, respectively.
+fn rearrange(input: &str) -> Result<(Piles, Piles)> {
+    let (piles, moves) = procedure(input)?;
+    let mut crate_mover_9000 = piles.clone();
+    let mut crate_mover_9001 = piles;
+
+    for Move {
+        count,
+        from,
+        to,
+        line,
+    } in moves
+    {
+        if crate_mover_9000.piles[from].len() < count as usize {
+            let message = format!("pile {} doesn't have {} crates to move", from + 1, count);
+            let excerpt = input.lines().nth(line - 1).unwrap_or("");
+            return Err(ParseError::new(message, line, 1, excerpt).into());
+        }
+        crate_mover_9000.shuffle(count, from, to);
+        crate_mover_9001.move_(count, from, to);
+    }
+
+    Ok((crate_mover_9000, crate_mover_9001))
+}
+
+#[test]
+fn exampleinput() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part1(input).unwrap(), "CMZ");
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part2(input).unwrap(), "MCD");
+}
+
+#[test]
+fn malformed() {
+    let drawing = "[A]    \n[B] [C]\n 1   2 \n\n";
+
+    let error = solve_part1(format!("{}move 1 from 3 to 1\n", drawing).as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (5, 13));
+
+    let error = solve_part1(format!("{}move 2 from 2 to 1\n", drawing).as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (5, 1));
+
+    let error = solve_part1(format!("{}move one from 2 to 1\n", drawing).as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (5, 6));
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub fn parse(input: impl Read) -> Result<impl Sized> {
+    procedure(&io::read_to_string(input)?)
+}
+
+/// The crates on top of each pile after moving them one at a time.
+pub fn solve_part1(input: impl Read) -> Result<String> {
+    Ok(rearrange(&io::read_to_string(input)?)?.0.tops())
+}
+
+/// The crates on top of each pile after moving them several at once.
+pub fn solve_part2(input: impl Read) -> Result<String> {
+    Ok(rearrange(&io::read_to_string(input)?)?.1.tops())
+}
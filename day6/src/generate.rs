@@ -0,0 +1,17 @@
+use aoc_core::Rng;
+
+/// A random datastream of `length` characters (at least 14), ending in a start-of-message marker.
+/// Before that, only a few different letters are used, so that the markers come late.
+pub fn generate(rng: &mut Rng, length: usize) -> String {
+    let length = length.max(14);
+    // Too few letters for a start-of-message marker, and maybe for a start-of-packet marker too.
+    let letters = rng.between(3, 13) as u8;
+    let mut input: String = (0..length - 14)
+        .map(|_| char::from(b'a' + rng.below(letters.into()) as u8))
+        .collect();
+    let mut marker: Vec<u8> = (b'a'..=b'z').collect();
+    rng.shuffle(&mut marker);
+    input.extend(marker[..14].iter().map(|&c| char::from(c)));
+    input.push('\n');
+    input
+}
@@ -0,0 +1,101 @@
+mod generate;
+mod part1;
+mod part2;
+mod shift;
+
+pub use generate::generate;
+
+use aoc_core::{ParseError, Result};
+use std::io::{self, Read};
+
+#[test]
+fn exampleinput() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part1(input).unwrap(), 7);
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part2(input).unwrap(), 19);
+}
+
+#[test]
+fn no_marker() {
+    for input in ["", "ab", "aaaa", "abab\n"] {
+        let error = solve_part1(input.as_bytes()).unwrap_err();
+        assert_eq!(
+            error.as_parse().unwrap().message(),
+            "no start-of-packet marker"
+        );
+    }
+    for input in ["", "cjhw", "abcdefghijklm", "abcdefghijklmabcdefghijklm\n"] {
+        let error = solve_part2(input.as_bytes()).unwrap_err();
+        assert_eq!(
+            error.as_parse().unwrap().message(),
+            "no start-of-message marker"
+        );
+    }
+}
+
+#[test]
+fn part2_matches_naive_scan() {
+    use aoc_check::{ints, strings, Check};
+
+    // A marker, fourteen letters in a row of the alphabet, planted between random letters
+    // (which may well make an earlier marker with it). Or, for a marker past the end of the
+    // alphabet, none at all: the letters then come from only thirteen, which can't make one.
+    const ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+    let letters = || strings(&ALPHABET[..16], 0);
+    let streams = ((letters(), ints(0, 13)), letters());
+    Check::new().property(&streams, |((before, marker), after)| {
+        let stream = match ALPHABET.get(*marker as usize..*marker as usize + 14) {
+            Some(marker) => format!("{}{}{}", before, marker, after),
+            None => (before.bytes().chain(after.bytes()))
+                .map(|c| char::from(b'a' + (c - b'a') % 13))
+                .collect(),
+        };
+        let expected = (14..=stream.len()).find(|&end| {
+            let window = &stream.as_bytes()[end - 14..end];
+            (1..14).all(|i| !window[..i].contains(&window[i]))
+        });
+        let got = solve_part2(stream.as_bytes()).map_err(|e| e.to_string());
+        match (expected, got) {
+            (Some(expected), Ok(got)) if got == expected => Ok(()),
+            (None, Err(e)) if e.starts_with("no start-of-message marker") => Ok(()),
+            (expected, got) => Err(format!("expected {:?}, got {:?}", expected, got)),
+        }
+    });
+}
+
+/// Reads the datastream without solving anything, so that reading can be timed on its own; the
+/// solvers look at one character at a time, so there is nothing else to parse.
+pub fn parse(mut input: impl Read) -> Result<impl Sized> {
+    let mut datastream = vec![];
+    input.read_to_end(&mut datastream)?;
+    Ok(datastream)
+}
+
+/// Streams the datastream, without its line ending, through `solve`, reporting a missing marker at
+/// the end of the input.
+fn find_marker<T>(
+    mut input: impl Read,
+    marker: &str,
+    solve: impl FnOnce(&[u8]) -> io::Result<Option<T>>,
+) -> Result<T> {
+    let mut datastream = vec![];
+    input.read_to_end(&mut datastream)?;
+    while let Some(b'\n' | b'\r') = datastream.last() {
+        datastream.pop();
+    }
+    solve(&datastream)?.ok_or_else(|| {
+        let message = format!("no {} marker", marker);
+        ParseError::at_end(message, &String::from_utf8_lossy(&datastream)).into()
+    })
+}
+
+/// The number of characters processed before the first start-of-packet marker.
+pub fn solve_part1(input: impl Read) -> Result<u32> {
+    find_marker(input, "start-of-packet", |stream| part1::solve(stream))
+}
+
+/// The number of characters processed before the first start-of-message marker.
+pub fn solve_part2(input: impl Read) -> Result<usize> {
+    find_marker(input, "start-of-message", |stream| part2::solve(stream))
+}
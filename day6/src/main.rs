@@ -1,8 +1,17 @@
-mod shift;
-mod part1;
-mod part2;
+use aoc_core::{InFile, Input, Report};
 
-fn main() {
-    part1::main();
-    part2::main();
-}
\ No newline at end of file
+fn main() -> aoc_core::Result<()> {
+    let input = Input::from_args(concat!(env!("CARGO_MANIFEST_DIR"), "/input"))?;
+    let report = Report::from_args(6)?;
+    report.part(
+        1,
+        "Ate {} characters before getting a halt sequence.",
+        || day6::solve_part1(input.as_bytes()).in_file(input.name()),
+    )?;
+    report.part(
+        2,
+        "Ate {} characters consumed before finding a start-of-message sequence.",
+        || day6::solve_part2(input.as_bytes()).in_file(input.name()),
+    )?;
+    Ok(())
+}
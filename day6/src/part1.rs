@@ -1,5 +1,5 @@
-use std::{io::{BufReader, Read}, fs::File};
 use crate::shift::CircularBuffer;
+use std::io::{self, BufReader, Read};
 
 struct Stream {
     history: CircularBuffer<3, char>,
@@ -39,7 +39,7 @@ impl Stream {
                 all_different = false;
                 break;
             }
-            for j in (i+1)..3 {
+            for j in (i + 1)..3 {
                 let jth_element = *self.history.ith_newest(j);
                 if ith_element == jth_element {
                     all_different = false;
@@ -51,19 +51,25 @@ impl Stream {
     }
 }
 
-pub fn main() {
-    let mut input = BufReader::new(File::open("input").expect("input to exist and be readable"));
+/// Counts the characters consumed before the last four characters are all different, unless the
+/// input runs out first.
+pub fn solve(input: impl Read) -> io::Result<Option<u32>> {
+    let mut input = BufReader::new(input).bytes();
     let mut first_three_chars = [0_u8; 3];
-    input.read_exact(&mut first_three_chars).expect("to be able to read the first three characters");
+    for c in &mut first_three_chars {
+        match input.next() {
+            Some(next) => *c = next?,
+            None => return Ok(None),
+        }
+    }
     let mut stream = Stream::new(first_three_chars);
-    
-    for c in input.bytes() {
-        let c = c.expect("every byte to be readable") as char;
-        let halt = stream.eat(c);
+
+    for c in input {
+        let halt = stream.eat(c? as char);
         if halt {
-            break;
+            return Ok(Some(stream.count));
         }
     }
-    
-    println!("Ate {} characters before getting a halt sequence.", stream.count);
+
+    Ok(None)
 }
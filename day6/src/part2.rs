@@ -1,37 +1,29 @@
-use std::{
-    fs::File,
-    io::{BufReader, Read}, path::Path,
-};
-
 use crate::shift::CircularBuffer;
+use std::io::{self, BufReader, Read};
 
-fn open_file_as_char_iter(file: impl AsRef<Path>) -> impl Iterator<Item = char> {
-    BufReader::new(File::open(file).expect("input to exist and be readable"))
-        .bytes()
-        .map(|b| b.expect("every byte to be readable") as char)
-}
-
-pub fn main() {
-    let mut input = open_file_as_char_iter("input");
+/// Counts the characters consumed before the last fourteen characters are all different, unless
+/// the input runs out first.
+pub fn solve(input: impl Read) -> io::Result<Option<usize>> {
+    let mut input = BufReader::new(input).bytes().map(|b| b.map(char::from));
     let mut history = CircularBuffer::<14, char>::from([' '; 14]);
 
     // Take the first 14 characters to fill the buffer.
-    for c in input.by_ref().take(14) {
-        history.push(c);
+    if !shift(&mut history, &mut input, 14)? {
+        return Ok(None);
     }
-    
+
     // Then, consume the rest of the input.
     let mut count = 14;
     'outer: loop {
         for i in (1..=13).rev() {
             let ith = *history.ith_oldest(i);
-            for j in (0..=i-1).rev() {
+            for j in (0..i).rev() {
                 let jth = *history.ith_oldest(j);
                 if ith == jth {
-                    // Shift the history until the ith element is gone.
-                    count +=  i;
-                    for c in input.by_ref().take(i) {
-                        history.push(c);
+                    // Shift the history until the jth (the older) element is gone.
+                    count += j + 1;
+                    if !shift(&mut history, &mut input, j + 1)? {
+                        return Ok(None);
                     }
                     continue 'outer;
                 }
@@ -39,6 +31,21 @@ pub fn main() {
         }
         break;
     }
-    
-    println!("Ate {} characters consumed before finding a start-of-message sequence.", count);
+
+    Ok(Some(count))
+}
+
+/// Pushes the next `n` characters into the history, returning whether there were that many.
+fn shift(
+    history: &mut CircularBuffer<14, char>,
+    input: &mut impl Iterator<Item = io::Result<char>>,
+    n: usize,
+) -> io::Result<bool> {
+    for _ in 0..n {
+        match input.next() {
+            Some(c) => history.push(c?),
+            None => return Ok(false),
+        }
+    }
+    Ok(true)
 }
@@ -26,6 +26,10 @@ impl FsFile {
 type Metadata = BTreeMap<FsFileId, BTreeMap<String, FsFileId>>;
 type Inodes = BTreeMap<FsFileId, FsFile>;
 
+/// Returned when trying to `cd` into a file.
+#[derive(Debug)]
+pub struct NotADirectory;
+
 #[derive(Debug)]
 pub struct FileSystem {
     free_id: FsFileId,
@@ -81,12 +85,14 @@ impl FileSystem {
         fsid
     }
 
-    pub fn cd(&mut self, dir_name: String) {
+    /// Moves into `dir_name`, which is created if it wasn't listed yet.
+    /// Fails if `dir_name` is a file.
+    pub fn cd(&mut self, dir_name: String) -> Result<(), NotADirectory> {
         // Edge case: cd ..
         if dir_name == ".." {
             let pwd_inode = self.inodes.get(&self.pwd).expect("pwd to have an inode");
             self.pwd = pwd_inode.parent;
-            return;
+            return Ok(());
         }
 
         // Check if the directory is already listed
@@ -102,7 +108,7 @@ impl FileSystem {
 
             // Something of this name exists; check that it's a directory
             if !child_inode.is_directory() {
-                panic!("Attempted to cd into a file.");
+                return Err(NotADirectory);
             }
 
             // It's a directory, and it already existed. Move the pwd there.
@@ -113,6 +119,7 @@ impl FileSystem {
             let new_fsid = self.create_dir(dir_name);
             self.pwd = new_fsid;
         }
+        Ok(())
     }
 
     pub fn put_file(&mut self, name: String, size: u64) {
@@ -134,7 +141,7 @@ impl FileSystem {
         Self::measure_sizes(&self.metadata, &mut self.inodes, &ROOT_FID);
         self
     }
-    
+
     pub fn occupied_size(&self) -> u64 {
         self.inodes.get(&ROOT_FID).expect("root to have inode").size
     }
@@ -154,7 +161,7 @@ impl FileSystem {
     }
 
     pub fn total_size_below(&self, limit: u64) -> u64 {
-        return self.total_size_below_recursive(limit, &ROOT_FID);
+        self.total_size_below_recursive(limit, &ROOT_FID)
     }
 
     fn total_size_below_recursive(&self, limit: u64, root: &FsFileId) -> u64 {
@@ -170,7 +177,7 @@ impl FileSystem {
                 total += self.total_size_below_recursive(limit, child_id);
             }
         }
-        return total;
+        total
     }
 
     pub fn delete_to_free(&self, to_free: u64) -> u64 {
@@ -180,7 +187,6 @@ impl FileSystem {
             return 0;
         }
         let need = to_free - currently_free;
-        println!("Need to free up {}", need);
         let to_delete = self
             .delete_to_free_recursive(need, &ROOT_FID)
             .expect("there to be an answer");
@@ -201,10 +207,10 @@ impl FileSystem {
             if !child_inode.is_directory() || child_inode.size < min_size {
                 continue;
             }
-            
+
             // See if there's a directory inside this one that's smaller but still suffices.
             let child_candidate = self.delete_to_free_recursive(min_size, child_id);
-            
+
             // If no sub-directory satisfies, the parent directory does.
             let candidate = child_candidate.unwrap_or(*child_id);
             let candidate_inode = self.inodes.get(&candidate).expect("candidate to have an inode");
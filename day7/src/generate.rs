@@ -0,0 +1,74 @@
+use aoc_core::Rng;
+
+/// The disk space used by the generated files is between these, so that part 2 has to delete
+/// something, but can.
+const USED: (u64, u64) = (41_000_000, 69_000_000);
+
+#[derive(Default)]
+struct Directory {
+    directories: Vec<(String, usize)>,
+    files: Vec<(String, u64)>,
+}
+
+/// A random terminal session exploring a file system with `files` files in it.
+pub fn generate(rng: &mut Rng, files: usize) -> String {
+    let files = files.max(1);
+    let mut directories = vec![Directory::default()];
+    for _ in 0..files / 4 + 1 {
+        let parent = rng.index(directories.len());
+        let name = name(rng, &directories[parent]);
+        let child = directories.len();
+        directories[parent].directories.push((name, child));
+        directories.push(Directory::default());
+    }
+
+    // Weigh the files at random, then size them so that they add up to the space used.
+    let weights: Vec<u64> = (0..files).map(|_| rng.between(1, 1000)).collect();
+    let total_weight: u64 = weights.iter().sum();
+    let used = rng.between(USED.0, USED.1);
+    for weight in weights {
+        let directory = rng.index(directories.len());
+        let name = format!(
+            "{}.{}",
+            name(rng, &directories[directory]),
+            rng.choose(&["txt", "dat", "log"])
+        );
+        let size = (weight * used / total_weight).max(1);
+        directories[directory].files.push((name, size));
+    }
+
+    let mut input = "$ cd /\n".to_string();
+    list(&directories, 0, &mut input);
+    input
+}
+
+/// A name that isn't taken in the `directory`.
+fn name(rng: &mut Rng, directory: &Directory) -> String {
+    loop {
+        let name: String = (0..rng.between(1, 8))
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect();
+        let taken = directory.directories.iter().any(|(n, _)| *n == name)
+            || directory.files.iter().any(|(n, _)| n.starts_with(&name));
+        if !taken {
+            return name;
+        }
+    }
+}
+
+/// Lists the directory, then explores each directory in it.
+fn list(directories: &[Directory], index: usize, input: &mut String) {
+    let directory = &directories[index];
+    input.push_str("$ ls\n");
+    for (name, _) in &directory.directories {
+        input.push_str(&format!("dir {}\n", name));
+    }
+    for (name, size) in &directory.files {
+        input.push_str(&format!("{} {}\n", size, name));
+    }
+    for (name, child) in &directory.directories {
+        input.push_str(&format!("$ cd {}\n", name));
+        list(directories, *child, input);
+        input.push_str("$ cd ..\n");
+    }
+}
@@ -0,0 +1,82 @@
+mod fs;
+mod generate;
+mod parse;
+
+pub use generate::generate;
+
+use aoc_core::{ParseError, Result};
+use std::io::{BufRead, BufReader, Read};
+
+#[derive(Debug)]
+pub enum Entry {
+    User(Command),
+    Directory(String),
+    File(u64, String),
+}
+
+#[derive(Debug)]
+pub enum Command {
+    Cd(String),
+    Ls,
+}
+
+/// Replays the terminal session, returning the file system it uncovered.
+fn explore(input: impl Read) -> Result<fs::FileSystem> {
+    let mut fs = fs::FileSystem::new(70_000_000);
+
+    // Enumerate the file system
+    for (line_no, line) in BufReader::new(input).lines().enumerate() {
+        let line = line?;
+        let entry = parse::entry(&line, line_no + 1)?;
+        match entry {
+            Entry::User(Command::Cd(dir)) => fs.cd(dir).map_err(|fs::NotADirectory| {
+                ParseError::new("cannot cd into a file", line_no + 1, 6, &line)
+            })?,
+            Entry::User(Command::Ls) => { /* File listings will follow */ }
+            Entry::Directory(_dir) => { /* Dir is only important if we cd into it */ }
+            Entry::File(size, name) => fs.put_file(name, size),
+        }
+    }
+
+    Ok(fs.finalize())
+}
+
+#[test]
+fn exampleinput() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part1(input).unwrap(), 95437);
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part2(input).unwrap(), 24933642);
+}
+
+#[test]
+fn malformed() {
+    let error = solve_part1("$ cd /\n$ ls\n12 a.txt\n$ rm a.txt\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (4, 3));
+
+    let error = solve_part1("$ cd /\n$ ls\n12 a.txt\n$ cd a.txt\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (4, 6));
+
+    let error = solve_part1("$ cd /\n$ ls\ndir a b\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (3, 7));
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub fn parse(input: impl Read) -> Result<impl Sized> {
+    explore(input)
+}
+
+/// The sum of the sizes of the directories of size at most 100000.
+pub fn solve_part1(input: impl Read) -> Result<u64> {
+    // Search recursively for directories with size less than a limit.
+    Ok(explore(input)?.total_size_below(100_000))
+}
+
+/// The size of the smallest directory that frees up enough space for the update.
+pub fn solve_part2(input: impl Read) -> Result<u64> {
+    // Search recursively for a directory to delete
+    Ok(explore(input)?.delete_to_free(30_000_000))
+}
@@ -1,45 +1,17 @@
-use std::io::BufRead;
-use std::{fs::File, io::BufReader};
+use aoc_core::{InFile, Input, Report};
 
-mod parse;
-mod fs;
-
-#[derive(Debug)]
-pub enum Entry {
-    User(Command),
-    Directory(String),
-    File(u64, String),
-}
-
-#[derive(Debug)]
-pub enum Command {
-    Cd(String),
-    Ls,
-}
-
-fn main() {
-    let input = BufReader::new(File::open("input").expect("input file to exist and be readable"));
-
-    let mut fs = fs::FileSystem::new(70_000_000);
-
-    // Enumerate the file system
-    for line in input.lines() {
-        let line = line.expect("to be able to read every line");
-        let entry = parse::entry(&line);
-        match entry {
-            Entry::User(Command::Cd(dir)) => fs.cd(dir),
-            Entry::User(Command::Ls) => { /* File listings will follow */ }
-            Entry::Directory(_dir) => { /* Dir is only important if we cd into it */ }
-            Entry::File(size, name) => fs.put_file(name, size),
-        }
-    }
-    
-    let fs = fs.finalize();
-    
-    // Search recursively for directories with size less than a limit.
-    println!("The sum of the sizes of the directories with at most 100000 is {}.", fs.total_size_below(100_000));
-        
-    // Search recursively for a directory to delete
-    println!("The system is occupying {}.", fs.occupied_size());
-    println!("To get enough space, we delete a directory of size {}.", fs.delete_to_free(30_000_000));
+fn main() -> aoc_core::Result<()> {
+    let input = Input::from_args(concat!(env!("CARGO_MANIFEST_DIR"), "/input"))?;
+    let report = Report::from_args(7)?;
+    report.part(
+        1,
+        "The sum of the sizes of the directories with at most 100000 is {}.",
+        || day7::solve_part1(input.as_bytes()).in_file(input.name()),
+    )?;
+    report.part(
+        2,
+        "To get enough space, we delete a directory of size {}.",
+        || day7::solve_part2(input.as_bytes()).in_file(input.name()),
+    )?;
+    Ok(())
 }
@@ -1,4 +1,5 @@
-use crate::{Entry, Command};
+use crate::{Command, Entry};
+use aoc_core::ParseError;
 use nom::bytes::complete::take_while;
 use nom::character::complete::{char, digit1};
 use nom::combinator::peek;
@@ -13,14 +14,14 @@ use nom::{
 
 fn word_delimiter(i: &str) -> nom::IResult<&str, &str> {
     if opt(eof)(i)?.1.is_some() {
-        return Ok((i, &""));
+        return Ok((i, ""));
     }
     let (rest, cr) = match opt(char('\r'))(i) {
         Ok((rest, cr)) => Ok((rest, cr.is_some())),
         Err(e) => Err(e),
     }?;
     match char('\n')(rest) {
-        Ok((rest, _)) => Ok((rest, if cr { &"\r\n" } else { &"\n" })),
+        Ok((rest, _)) => Ok((rest, if cr { "\r\n" } else { "\n" })),
         Err(e) => Err(e),
     }
 }
@@ -30,7 +31,7 @@ fn word(i: &str) -> nom::IResult<&str, String> {
     let mut rest = i;
     let mut escape = false;
     loop {
-        if rest == "" {
+        if rest.is_empty() {
             return Ok(("", word));
         }
         if escape {
@@ -65,43 +66,80 @@ fn directory_listing(i: &str) -> nom::IResult<&str, String> {
     preceded(tag("dir "), word)(i)
 }
 
-fn file_listing(i: &str) -> nom::IResult<&str, (u64, String)> {
+fn file_listing(i: &str) -> nom::IResult<&str, (&str, String)> {
     let (rest, (size, name)) = separated_pair(
         digit1,
         char(' '),
         take_while(|c: char| c.is_alphanumeric() || c == '.' || c == '_'),
     )(i)?;
-    let size = str::parse::<u64>(size).expect("the number to fit in a u64");
     Ok((rest, (size, name.to_string())))
 }
 
-fn parse_command(raw: Vec<String>) -> Entry {
+/// The (1-based) column at which `rest`, a suffix of `line`, starts.
+fn column(line: &str, rest: &str) -> usize {
+    line[..line.len() - rest.len()].chars().count() + 1
+}
+
+fn parse_command(raw: Vec<String>, line: &str, line_no: usize) -> Result<Entry, ParseError> {
+    let error = |message: String, column| ParseError::new(message, line_no, column, line);
+    // Words are separated by single spaces after the "$ " prompt; this is only used to point at
+    // them, so escapes don't need to be accounted for exactly.
+    let word_column = |n: usize| {
+        let skipped: usize = raw[..n].iter().map(|w| w.chars().count() + 1).sum();
+        3 + skipped
+    };
     let (cmd, args) = raw.split_at(1);
     match cmd[0].as_str() {
-        "cd" => {
-            if args.len() > 1 {
-                println!("WARNING: Ignoring arguments to ls: {:?}", &args[1..]);
-            }
-            Entry::User(Command::Cd(args[0].to_owned()))
+        "cd" => match args {
+            [dir] => Ok(Entry::User(Command::Cd(dir.to_owned()))),
+            [] => Err(error(
+                "expected a directory to cd into".to_string(),
+                line.trim_end().chars().count() + 1,
+            )),
+            _ => Err(error(
+                format!("unexpected arguments to cd: {:?}", &args[1..]),
+                word_column(2),
+            )),
         },
         "ls" => {
-            if args.len() > 0 {
-                println!("WARNING: Ignoring arguments to ls: {:?}", args);
+            if !args.is_empty() {
+                return Err(error(
+                    format!("unexpected arguments to ls: {:?}", args),
+                    word_column(1),
+                ));
             }
-            Entry::User(Command::Ls)
-        },
-        cmd => panic!("Unknown command {}", cmd),
+            Ok(Entry::User(Command::Ls))
+        }
+        cmd => Err(error(format!("unknown command {:?}", cmd), 3)),
     }
 }
 
-pub fn entry(i: &str) -> Entry {
-    let (rest, entry) = command(i)
-        .map(|(rest, command)| (rest, parse_command(command)))
-        .or(directory_listing(i).map(|(rest, name)| (rest, Entry::Directory(name))))
-        .or(file_listing(i).map(|(rest, (size, name))| (rest, Entry::File(size, name))))
-        .expect("Don't know how to read a line!");
-    if rest.trim().len() != 0 {
-        panic!("Trailing contents: {}", rest);
+/// Parses a line of the terminal session, where `line_no` is 1-based.
+pub fn entry(i: &str, line_no: usize) -> Result<Entry, ParseError> {
+    let (rest, entry) = if let Ok((rest, command)) = command(i) {
+        (rest, parse_command(command, i, line_no)?)
+    } else if let Ok((rest, name)) = directory_listing(i) {
+        (rest, Entry::Directory(name))
+    } else if let Ok((rest, (size, name))) = file_listing(i) {
+        let size = str::parse::<u64>(size)
+            .map_err(|_| ParseError::new("file size is too large", line_no, 1, i))?;
+        (rest, Entry::File(size, name))
+    } else {
+        return Err(ParseError::new(
+            "expected a command, a directory or a file",
+            line_no,
+            1,
+            i,
+        ));
+    };
+    if !rest.trim().is_empty() {
+        let trailing = rest.trim_start();
+        return Err(ParseError::new(
+            format!("unexpected trailing contents {:?}", trailing.trim_end()),
+            line_no,
+            column(i, trailing),
+            i,
+        ));
     }
-    entry
+    Ok(entry)
 }
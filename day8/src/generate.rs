@@ -0,0 +1,14 @@
+use aoc_core::Rng;
+
+/// A random `side` by `side` grid of tree heights.
+pub fn generate(rng: &mut Rng, side: usize) -> String {
+    let side = side.max(1);
+    let mut input = String::with_capacity((side + 1) * side);
+    for _ in 0..side {
+        for _ in 0..side {
+            input.push(char::from(b'0' + rng.below(10) as u8));
+        }
+        input.push('\n');
+    }
+    input
+}
@@ -0,0 +1,85 @@
+mod generate;
+
+pub use generate::generate;
+
+use aoc_core::Result;
+use aoc_grid::{Direction, Grid, Point};
+use std::io::{self, Read};
+
+pub type Height = u8;
+
+#[test]
+fn exampleinput() {
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part1(input).unwrap(), 21);
+    let input = std::fs::File::open("exampleinput").unwrap();
+    assert_eq!(solve_part2(input).unwrap(), 8);
+}
+
+#[test]
+fn malformed() {
+    let error = solve_part1("303\n2x5\n653\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (2, 2));
+
+    let error = solve_part1("303\n255\n65\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (3, 3));
+}
+
+/// The number of trees visible from outside the grid.
+pub fn solve_part1(input: impl Read) -> Result<usize> {
+    let map = parse(input)?;
+    // A tree is visible from outside if it's taller than every tree between it and some edge.
+    let visible = map
+        .iter()
+        .filter(|&(tree, &height)| {
+            Direction::ALL
+                .into_iter()
+                .any(|direction| map.ray(tree, direction).all(|(_, &other)| other < height))
+        })
+        .count();
+    Ok(visible)
+}
+
+type Visibility = u32;
+
+/// The highest scenic score possible for any tree.
+pub fn solve_part2(input: impl Read) -> Result<Visibility> {
+    let map = parse(input)?;
+    let best = map
+        .points()
+        .map(|tree| {
+            Direction::ALL
+                .into_iter()
+                .map(|direction| viewing_distance(&map, tree, direction))
+                .product()
+        })
+        .max()
+        .expect("some answer");
+    Ok(best)
+}
+
+/// The number of trees seen looking from `from` in `direction`, up to (and including) the first
+/// one at least as tall, or to the edge.
+fn viewing_distance(map: &Grid<Height>, from: Point, direction: Direction) -> Visibility {
+    let height = map[from];
+    let mut seen = 0;
+    for (_, &other) in map.ray(from, direction) {
+        seen += 1;
+        if other >= height {
+            break;
+        }
+    }
+    seen
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub fn parse(input: impl Read) -> Result<Grid<Height>> {
+    let map = Grid::parse(&io::read_to_string(input)?, |_, c| {
+        c.to_digit(10)
+            .map(|d| d as Height)
+            .ok_or_else(|| format!("expected a tree height (a digit), found {:?}", c))
+    })?;
+    Ok(map)
+}
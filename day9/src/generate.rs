@@ -0,0 +1,11 @@
+use aoc_core::Rng;
+
+/// A random series of `motions` motions of the head of the rope.
+pub fn generate(rng: &mut Rng, motions: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..motions {
+        let direction = rng.choose(&['U', 'D', 'L', 'R']);
+        input.push_str(&format!("{} {}\n", direction, rng.between(1, 20)));
+    }
+    input
+}
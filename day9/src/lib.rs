@@ -0,0 +1,178 @@
+use aoc_core::Result;
+use aoc_grid::{Direction, Point};
+use aoc_parse::{Parser, Reader, Source};
+use std::{
+    collections::BTreeSet,
+    fmt::Debug,
+    io::{BufReader, Read},
+};
+
+use crate::part1::TwoKnotRope;
+
+mod generate;
+mod part1;
+
+pub use generate::generate;
+
+type Count = u16;
+
+#[derive(Clone, Copy)]
+pub struct Movement {
+    direction: Direction,
+    count: Count,
+}
+
+struct MovementSeq<R: Read> {
+    parser: Parser<Reader<BufReader<R>>>,
+}
+
+struct Rope<const N: usize> {
+    knots: [Point; N],
+    visited: BTreeSet<Point>,
+}
+
+impl<R: Read> MovementSeq<R> {
+    fn new(input: R) -> Self {
+        MovementSeq {
+            parser: Parser::from_reader(BufReader::new(input)),
+        }
+    }
+}
+
+impl<R: Read> Iterator for MovementSeq<R> {
+    type Item = Result<Movement>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.parser.done() {
+            Ok(true) => None,
+            Ok(false) => Some(Movement::parse(&mut self.parser)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl Movement {
+    /// Parses a line like `R 4`.
+    fn parse(parser: &mut Parser<impl Source>) -> Result<Self> {
+        let start = parser.position();
+        let direction = match parser.take() {
+            Some('U') => Direction::Up,
+            Some('D') => Direction::Down,
+            Some('L') => Direction::Left,
+            Some('R') => Direction::Right,
+            c => {
+                let message = format!("expected U, D, L or R, found {:?}", c.unwrap_or('\n'));
+                return Err(parser.error_at(message, start));
+            }
+        };
+        parser.match_(' ')?;
+        let count = parser.number()?;
+        parser.skip_spaces();
+        parser.end_of_line()?;
+        Ok(Movement { direction, count })
+    }
+}
+
+impl<const N: usize> Rope<N> {
+    fn new() -> Self {
+        let knots = [Point::ZERO; N];
+        let mut visited = BTreeSet::new();
+        visited.insert(*knots.last().unwrap());
+        Rope {
+            knots,
+            visited,
+        }
+    }
+
+    fn move_head(&mut self, movement: Movement) {
+        for _ in 0..movement.count {
+            // Move the head.
+            self.knots[0] += movement.direction;
+
+            for knot_index in 1..self.knots.len() {
+                let knot = self.knots[knot_index];
+                let parent = self.knots[knot_index - 1];
+
+                // If this knot does not need to move, the rest of the knots do not need to move.
+                if knot.chebyshev(parent) <= 1 {
+                    break;
+                }
+
+                // Now, moving this knot to the previous position of the parent no longer works.
+                // Instead I'll just follow the rules outlined in the problem: a step towards the
+                // parent, along its row or column if they share one, or diagonally otherwise.
+                self.knots[knot_index] += (parent - knot).signum();
+
+                if knot_index == self.knots.len() - 1 {
+                    self.visited.insert(*self.knots.last().unwrap());
+                }
+            }
+        }
+    }
+
+    fn count_tail_visited(&self) -> usize {
+        self.visited.len()
+    }
+}
+
+impl Debug for Movement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            match self.direction {
+                Direction::Up => "U",
+                Direction::Down => "D",
+                Direction::Left => "L",
+                Direction::Right => "R",
+            },
+            self.count
+        )
+    }
+}
+
+#[test]
+fn example1() {
+    let input = std::fs::File::open("exampleinput1").unwrap();
+    assert_eq!(solve_part1(input).unwrap(), 13)
+}
+
+#[test]
+fn example2() {
+    let input = std::fs::File::open("exampleinput2").unwrap();
+    assert_eq!(solve_part2(input).unwrap(), 36)
+}
+
+#[test]
+fn malformed() {
+    let error = solve_part1("R 4\nX 2\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (2, 1));
+
+    let error = solve_part1("R 4\nU -2\n".as_bytes()).unwrap_err();
+    let error = error.as_parse().unwrap();
+    assert_eq!((error.line(), error.column()), (2, 3));
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub fn parse(input: impl Read) -> Result<impl Sized> {
+    MovementSeq::new(input).collect::<Result<Vec<_>>>()
+}
+
+/// The number of positions visited by the tail of a rope with two knots.
+pub fn solve_part1(input: impl Read) -> Result<usize> {
+    let mut two_knot = TwoKnotRope::new();
+    for head_movement in MovementSeq::new(input) {
+        two_knot.move_head(head_movement?);
+    }
+    Ok(two_knot.count_tail_visited())
+}
+
+/// The number of positions visited by the tail of a rope with ten knots.
+pub fn solve_part2(input: impl Read) -> Result<usize> {
+    let mut ten_knot = Rope::<10>::new();
+    for head_movement in MovementSeq::new(input) {
+        ten_knot.move_head(head_movement?);
+    }
+    Ok(ten_knot.count_tail_visited())
+}
@@ -1,4 +1,5 @@
-use crate::{Movement, Point};
+use crate::Movement;
+use aoc_grid::Point;
 use core::fmt::Debug;
 use std::collections::BTreeSet;
 
@@ -11,18 +12,18 @@ pub struct TwoKnotRope {
 impl TwoKnotRope {
     pub fn new() -> Self {
         let mut visited = BTreeSet::new();
-        visited.insert(Point::zero());
+        visited.insert(Point::ZERO);
         TwoKnotRope {
-            head_position: Point::zero(),
-            tail_position: Point::zero(),
+            head_position: Point::ZERO,
+            tail_position: Point::ZERO,
             visited,
         }
     }
 
     pub fn move_head(&mut self, movement: Movement) {
         for _ in 0..movement.count {
-            let old_head_position = self.head_position.clone();
-            self.head_position = self.head_position + &movement.direction;
+            let old_head_position = self.head_position;
+            self.head_position += movement.direction;
 
             // Now, we just need to check whether the tail position needs to move, taking into account
             // the new head position.
@@ -34,14 +35,13 @@ impl TwoKnotRope {
 
             // Mark the position that the tail is now on.
             if tail_moved {
-                self.visited.insert(self.tail_position.clone());
+                self.visited.insert(self.tail_position);
             }
         }
     }
 
     fn adjust_tail(head: &Point, tail: &mut Point, old_head_position: Point) -> bool {
-        let tail_to_head = *head - *tail;
-        if tail_to_head.touching() {
+        if tail.chebyshev(*head) <= 1 {
             return false;
         }
         *tail = old_head_position;
@@ -55,7 +55,8 @@ impl TwoKnotRope {
 
 impl Debug for TwoKnotRope {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in (0..5).rev() {
+        // Up is towards negative `y`, so the top row is drawn first.
+        for i in -4..=0 {
             for j in 0..6 {
                 if self.head_position.x == j && self.head_position.y == i {
                     write!(f, "H")?;
@@ -69,9 +70,9 @@ impl Debug for TwoKnotRope {
                     write!(f, ".")?;
                 }
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
-        write!(f, "\n")?;
+        writeln!(f)?;
         Ok(())
     }
 }
\ No newline at end of file